@@ -0,0 +1,196 @@
+//! Times lookups in the bundled water table, run with `cargo bench --bench water_lookup`
+//!
+//! every lookup is also timed against a baseline that scans the rows of the table
+//! the way the table did before it was indexed, so the speedup can be reproduced on any machine
+
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+use steam_tables::{data, water::WaterTable};
+
+const QUERIES: usize = 200_000;
+
+/// columns of the bundled table used by the baseline
+const PRESSURE: usize = 0;
+const TEMPERATURE: usize = 1;
+const ENTHALPY: usize = 5;
+
+fn time_queries(name: &str, mut query: impl FnMut(usize)) -> Duration {
+    let start = Instant::now();
+    for index in 0..QUERIES {
+        query(index);
+    }
+    let elapsed = start.elapsed();
+
+    println!(
+        "{:<44} {:>10.3} µs/query",
+        name,
+        elapsed.as_secs_f64() * 1.0e6 / QUERIES as f64
+    );
+
+    elapsed
+}
+
+fn print_speedup(baseline: Duration, indexed: Duration) {
+    println!(
+        "{:<44} {:>10.1} x",
+        "speedup",
+        baseline.as_secs_f64() / indexed.as_secs_f64()
+    );
+}
+
+/// rows of the bundled water table without the phase column
+fn table_rows() -> Vec<Vec<f64>> {
+    data::COMPRESSED_LIQUID_SUPERHEATED_STEAM
+        .lines()
+        .filter_map(|line| {
+            let fields: Vec<&str> = line.split(',').map(str::trim).collect();
+            let values: Result<Vec<f64>, _> = fields[..fields.len() - 1]
+                .iter()
+                .map(|field| field.parse::<f64>())
+                .collect();
+            values.ok().filter(|values| !values.is_empty())
+        })
+        .collect()
+}
+
+/// rows bounding `target` in `column` among `rows`, found by scanning all of them
+fn scan_bounds<'a>(
+    rows: impl Iterator<Item = &'a Vec<f64>>,
+    column: usize,
+    target: f64,
+) -> Option<(&'a Vec<f64>, &'a Vec<f64>)> {
+    let mut lower: Option<&Vec<f64>> = None;
+    let mut upper: Option<&Vec<f64>> = None;
+
+    for row in rows {
+        if row[column] <= target {
+            if lower.is_none_or(|lower| row[column] >= lower[column]) {
+                lower = Some(row);
+            }
+        } else if upper.is_none_or(|upper| row[column] < upper[column]) {
+            upper = Some(row);
+        }
+    }
+
+    Some((lower?, upper.or(lower)?))
+}
+
+fn interpolate_rows(lower: &[f64], upper: &[f64], column: usize, target: f64) -> Vec<f64> {
+    let span = upper[column] - lower[column];
+    let fraction = if span == 0.0 {
+        0.0
+    } else {
+        (target - lower[column]) / span
+    };
+
+    lower
+        .iter()
+        .zip(upper)
+        .map(|(lower, upper)| lower + fraction * (upper - lower))
+        .collect()
+}
+
+/// pressures of the isobars bounding `pressure`, found by scanning every row
+fn scan_isobars(rows: &[Vec<f64>], pressure: f64) -> Option<(f64, f64)> {
+    let (lower, upper) = scan_bounds(rows.iter(), PRESSURE, pressure)?;
+    Some((lower[PRESSURE], upper[PRESSURE]))
+}
+
+/// baseline `get_values_at_point`, scanning the rows for the isobars and for the temperatures on each
+fn baseline_state_at_point(rows: &[Vec<f64>], pressure: f64, temperature: f64) -> Option<Vec<f64>> {
+    let (lower_pressure, upper_pressure) = scan_isobars(rows, pressure)?;
+
+    let mut isobar_states = [lower_pressure, upper_pressure].map(|isobar_pressure| {
+        let isobar = rows.iter().filter(|row| row[PRESSURE] == isobar_pressure);
+        let (lower, upper) = scan_bounds(isobar, TEMPERATURE, temperature)?;
+        Some(interpolate_rows(lower, upper, TEMPERATURE, temperature))
+    });
+
+    let upper = isobar_states[1].take()?;
+    let lower = isobar_states[0].take()?;
+    Some(interpolate_rows(&lower, &upper, PRESSURE, pressure))
+}
+
+/// baseline pressure-enthalpy flash, scanning the rows for the isobars and for the enthalpies on each
+fn baseline_pressure_enthalpy(rows: &[Vec<f64>], pressure: f64, enthalpy: f64) -> Option<Vec<f64>> {
+    let (lower_pressure, upper_pressure) = scan_isobars(rows, pressure)?;
+
+    let mut isobar_states = [lower_pressure, upper_pressure].map(|isobar_pressure| {
+        let isobar = rows.iter().filter(|row| row[PRESSURE] == isobar_pressure);
+        let (lower, upper) = scan_bounds(isobar, ENTHALPY, enthalpy)?;
+        Some(interpolate_rows(lower, upper, ENTHALPY, enthalpy))
+    });
+
+    let upper = isobar_states[1].take()?;
+    let lower = isobar_states[0].take()?;
+    Some(interpolate_rows(&lower, &upper, PRESSURE, pressure))
+}
+
+fn main() {
+    let water_table =
+        WaterTable::new(data::COMPRESSED_LIQUID_SUPERHEATED_STEAM.to_string()).unwrap();
+    let rows = table_rows();
+
+    // spread the queries over the superheated and supercritical region
+    let pressures: Vec<f64> = (0..97).map(|index| 0.05 + index as f64 * 0.25).collect();
+    let temperatures: Vec<f64> = (0..89).map(|index| 405.0 + index as f64 * 17.3).collect();
+    let enthalpy = |index: usize| 3300.0 + (index % 50) as f64 * 10.0;
+
+    let baseline = time_queries("baseline get_values_at_point (row scan)", |index| {
+        let pressure = pressures[index % pressures.len()];
+        let temperature = temperatures[index % temperatures.len()];
+        black_box(baseline_state_at_point(
+            black_box(&rows),
+            black_box(pressure),
+            black_box(temperature),
+        ))
+        .unwrap();
+    });
+
+    let indexed = time_queries("get_values_at_point", |index| {
+        let pressure = pressures[index % pressures.len()];
+        let temperature = temperatures[index % temperatures.len()];
+        black_box(water_table.get_values_at_point(black_box(pressure), black_box(temperature)))
+            .unwrap();
+    });
+    print_speedup(baseline, indexed);
+
+    time_queries("get_state_at_point", |index| {
+        let pressure = pressures[index % pressures.len()];
+        let temperature = temperatures[index % temperatures.len()];
+        black_box(water_table.get_state_at_point(black_box(pressure), black_box(temperature)))
+            .unwrap();
+    });
+
+    // the derived properties interpolate the states around the point
+    time_queries("get_state_with_derived_properties_at_point", |index| {
+        let pressure = pressures[index % pressures.len()];
+        let temperature = temperatures[index % temperatures.len()];
+        black_box(water_table.get_state_with_derived_properties_at_point(
+            black_box(pressure),
+            black_box(temperature),
+        ))
+        .unwrap();
+    });
+
+    let baseline = time_queries("baseline pressure-enthalpy flash (row scan)", |index| {
+        let pressure = pressures[index % pressures.len()];
+        black_box(baseline_pressure_enthalpy(
+            black_box(&rows),
+            black_box(pressure),
+            black_box(enthalpy(index)),
+        ))
+        .unwrap();
+    });
+
+    let indexed = time_queries("get_values_at_pressure_enthalpy", |index| {
+        let pressure = pressures[index % pressures.len()];
+        black_box(
+            water_table
+                .get_values_at_pressure_enthalpy(black_box(pressure), black_box(enthalpy(index))),
+        )
+        .unwrap();
+    });
+    print_speedup(baseline, indexed);
+}
@@ -0,0 +1,432 @@
+//! Generates the bundled heavy water tables, run with
+//! `cargo run --example heavy_water_tables -- src/data`
+//!
+//! the states of heavy water are mapped onto states of water given by IAPWS-IF97 through
+//! corresponding states, with a Gibbs energy of
+//!
+//! g_D2O(T, p) = λ g_H2O(T0(T), κ p) + A + B T
+//!
+//! κ is the ratio of the critical pressures and λ κ that of the critical densities, so the
+//! critical point of heavy water maps onto that of water, and T0(T) is the temperature at which
+//! the reduced vapor pressure of water equals that of heavy water at T. Saturated states of heavy
+//! water then map onto saturated states of water, and the volume, enthalpy and entropy follow from
+//! the derivatives of the Gibbs energy, which keeps the tables consistent with the Clapeyron equation.
+//! A and B put the internal energy and entropy of the saturated liquid at the triple point to zero.
+//!
+//! The vapor pressure of heavy water is given by a Wagner type equation which reproduces the
+//! triple point and the normal boiling point within 0.2 %. Densities are within about 0.3 % in the
+//! liquid and 2 % in the vapor, enthalpies of vaporization are about 3 % and heat capacities of the
+//! liquid up to 8 % low, so the tables do not replace the IAPWS formulation for heavy water.
+
+use std::{fs, io, path::Path};
+
+use steam_tables::constants::*;
+use steam_tables::data;
+use steam_tables::if97::{If97, If97Properties};
+
+/// (n_i, t_i) of ln(p / p_c) = T_c / T Σ n_i θ^t_i with θ = 1 - T / T_c
+const VAPOR_PRESSURE: [(f64, f64); 5] = [
+    (-8.0236, 1.0),
+    (2.3957, 1.5),
+    (-42.639, 2.75),
+    (99.569, 3.0),
+    (-62.135, 3.2),
+];
+
+/// temperature step in K of the derivative of the corresponding water temperature
+const TEMPERATURE_STEP: f64 = 1.0e-3;
+
+/// pressures in MPa of the isobars, those of the water table up to 90 MPa, above which the
+/// corresponding pressure of water leaves IAPWS-IF97
+const ISOBARS: &[f64] = &[
+    0.01, 0.02, 0.03, 0.04, 0.05, 0.06, 0.07, 0.08, 0.09, 0.1, 0.11, 0.12, 0.13, 0.14, 0.15, 0.16,
+    0.18, 0.2, 0.22, 0.24, 0.26, 0.28, 0.3, 0.35, 0.4, 0.45, 0.5, 0.55, 0.6, 0.65, 0.7, 0.75, 0.8,
+    0.9, 1.0, 1.1, 1.2, 1.3, 1.4, 1.5, 1.6, 1.8, 2.0, 2.2, 2.5, 3.0, 3.5, 4.0, 4.5, 5.0, 5.5, 6.0,
+    6.5, 7.0, 7.5, 8.0, 9.0, 10.0, 11.0, 12.0, 13.0, 14.0, 15.0, 16.0, 17.0, 18.0, 19.0, 20.0,
+    22.0, 25.0, 30.0, 35.0, 40.0, 45.0, 50.0, 60.0, 70.0, 80.0, 90.0,
+];
+
+/// highest temperature of the water table in °C, the corresponding temperature of water
+/// stays below the 800 °C up to which IAPWS-IF97 holds at 90 MPa
+const MAXIMUM_TEMPERATURE: f64 = 780.0;
+
+const PREAMBLE: &str = "fluid,D2O
+version,1.0
+source,generated by examples/heavy_water_tables.rs from IAPWS-IF97 by corresponding states
+date,10/18/2026
+";
+
+/// Maps states of heavy water onto states of water
+struct CorrespondingStates {
+    if97: If97,
+    /// ratio of the critical pressure of water to that of heavy water
+    pressure_ratio: f64,
+    /// λ, the specific volume of heavy water is λ κ that of water
+    gibbs_ratio: f64,
+    /// slope of T0(T) above the critical temperature
+    critical_slope: f64,
+    /// A in kJ/kg and B in kJ/(kg K)
+    reference: (f64, f64),
+}
+
+/// State of heavy water in the units of the tables
+#[derive(Debug, Clone, Copy)]
+struct State {
+    pressure: f64,
+    temperature: f64,
+    specific_volume: f64,
+    internal_energy: f64,
+    enthalpy: f64,
+    entropy: f64,
+}
+
+/// vapor pressure of heavy water in MPa at `temperature` in K
+fn vapor_pressure(temperature: f64) -> f64 {
+    let critical_temperature = HEAVY_WATER_CRITICAL_TEMPERATURE + KELVIN_OFFSET;
+    let theta = 1.0 - temperature / critical_temperature;
+
+    let sum: f64 = VAPOR_PRESSURE.iter().map(|(n, t)| n * theta.powf(*t)).sum();
+
+    HEAVY_WATER_CRITICAL_PRESSURE * (critical_temperature / temperature * sum).exp()
+}
+
+/// saturation temperature of heavy water in K at `pressure` in MPa, by bisection
+fn saturation_temperature(pressure: f64) -> f64 {
+    let mut lower = HEAVY_WATER_TRIPLE_POINT_TEMPERATURE + KELVIN_OFFSET - 1.0;
+    let mut upper = HEAVY_WATER_CRITICAL_TEMPERATURE + KELVIN_OFFSET;
+
+    for _ in 0..200 {
+        let middle = 0.5 * (lower + upper);
+        if vapor_pressure(middle) < pressure {
+            lower = middle;
+        } else {
+            upper = middle;
+        }
+    }
+
+    0.5 * (lower + upper)
+}
+
+impl CorrespondingStates {
+    fn new() -> CorrespondingStates {
+        let pressure_ratio = CRITICAL_PRESSURE / HEAVY_WATER_CRITICAL_PRESSURE;
+        let volume_ratio = HEAVY_WATER_CRITICAL_DENSITY.recip() / CRITICAL_DENSITY.recip();
+
+        let mut states = CorrespondingStates {
+            if97: If97::new(),
+            pressure_ratio,
+            gibbs_ratio: volume_ratio / pressure_ratio,
+            critical_slope: 0.0,
+            reference: (0.0, 0.0),
+        };
+
+        // the slope of T0 at the critical point continues above it
+        let critical_temperature = HEAVY_WATER_CRITICAL_TEMPERATURE + KELVIN_OFFSET;
+        states.critical_slope = (CRITICAL_TEMPERATURE_KELVIN
+            - states.water_temperature(critical_temperature - TEMPERATURE_STEP))
+            / TEMPERATURE_STEP;
+
+        let triple_point = HEAVY_WATER_TRIPLE_POINT_TEMPERATURE + KELVIN_OFFSET;
+        let (liquid, _) = states.saturated_states(triple_point);
+        let (a, b) = (liquid.internal_energy, liquid.entropy);
+        states.reference = (-a, b);
+
+        states
+    }
+
+    /// T0 in K, the temperature of water corresponding to `temperature` of heavy water in K
+    fn water_temperature(&self, temperature: f64) -> f64 {
+        let critical_temperature = HEAVY_WATER_CRITICAL_TEMPERATURE + KELVIN_OFFSET;
+
+        if temperature >= critical_temperature {
+            return CRITICAL_TEMPERATURE_KELVIN
+                + self.critical_slope * (temperature - critical_temperature);
+        }
+
+        let water_pressure =
+            (self.pressure_ratio * vapor_pressure(temperature)).min(CRITICAL_PRESSURE);
+        self.if97.saturation_temperature(water_pressure).unwrap() + KELVIN_OFFSET
+    }
+
+    /// dT0/dT, one sided just below the critical temperature
+    fn water_temperature_slope(&self, temperature: f64) -> f64 {
+        let critical_temperature = HEAVY_WATER_CRITICAL_TEMPERATURE + KELVIN_OFFSET;
+        let upper = (temperature + TEMPERATURE_STEP).min(critical_temperature);
+        let lower = upper - 2.0 * TEMPERATURE_STEP;
+
+        if temperature >= critical_temperature {
+            return self.critical_slope;
+        }
+
+        (self.water_temperature(upper) - self.water_temperature(lower)) / (upper - lower)
+    }
+
+    /// State of heavy water at `pressure` in MPa and `temperature` in K from the state of
+    /// water `water` at the corresponding point
+    fn state(&self, pressure: f64, temperature: f64, water: &If97Properties) -> State {
+        let water_temperature = water.temperature;
+        let slope = self.water_temperature_slope(temperature);
+        let (a, b) = self.reference;
+
+        let specific_volume = self.gibbs_ratio * self.pressure_ratio * water.specific_volume;
+        let entropy = self.gibbs_ratio * slope * water.entropy - b;
+        let enthalpy = self.gibbs_ratio
+            * (water.enthalpy + (temperature * slope - water_temperature) * water.entropy)
+            + a;
+
+        State {
+            pressure,
+            temperature: temperature - KELVIN_OFFSET,
+            specific_volume,
+            // MPa m^3/kg are 1000 kJ/kg
+            internal_energy: enthalpy - 1000.0 * pressure * specific_volume,
+            enthalpy,
+            entropy,
+        }
+    }
+
+    /// state of heavy water at `pressure` in MPa and `temperature` in K off the saturation line
+    fn single_phase_state(&self, pressure: f64, temperature: f64) -> State {
+        let water_temperature = self.water_temperature(temperature);
+        let water = self
+            .if97
+            .properties(
+                self.pressure_ratio * pressure,
+                water_temperature - KELVIN_OFFSET,
+            )
+            .unwrap();
+
+        self.state(pressure, temperature, &water)
+    }
+
+    /// saturated liquid and vapor of heavy water at `temperature` in K
+    fn saturated_states(&self, temperature: f64) -> (State, State) {
+        let pressure = vapor_pressure(temperature);
+        let water_temperature = self.water_temperature(temperature);
+        let (liquid, vapor) = self
+            .if97
+            .saturation_properties(water_temperature - KELVIN_OFFSET)
+            .unwrap();
+
+        (
+            self.state(pressure, temperature, &liquid),
+            self.state(pressure, temperature, &vapor),
+        )
+    }
+
+    /// saturated states at the critical point, where both phases are the critical state
+    fn critical_states(&self) -> (State, State) {
+        let critical_temperature = HEAVY_WATER_CRITICAL_TEMPERATURE + KELVIN_OFFSET;
+        let critical_state =
+            self.single_phase_state(HEAVY_WATER_CRITICAL_PRESSURE, critical_temperature);
+        (critical_state, critical_state)
+    }
+}
+
+fn significant(value: f64, digits: i32) -> String {
+    if value == 0.0 {
+        return "0".to_string();
+    }
+
+    let decimals = (digits - 1 - value.abs().log10().floor() as i32).max(0) as usize;
+    let text = format!("{:.*}", decimals, value);
+    if text.contains('.') {
+        text.trim_end_matches('0').trim_end_matches('.').to_string()
+    } else {
+        text
+    }
+}
+
+fn decimals(value: f64, decimals: usize) -> String {
+    let text = format!("{:.*}", decimals, value);
+    let text = if text.contains('.') {
+        text.trim_end_matches('0').trim_end_matches('.')
+    } else {
+        &text
+    };
+
+    // negative zero is written without its sign
+    if text == "-0" {
+        "0".to_string()
+    } else {
+        text.to_string()
+    }
+}
+
+/// values of a row of the saturated tables after the temperature and pressure
+fn saturated_values((liquid, vapor): (State, State)) -> String {
+    [
+        significant(liquid.specific_volume, 6),
+        significant(vapor.specific_volume, 6),
+        decimals(liquid.internal_energy, 2),
+        decimals(vapor.internal_energy, 2),
+        decimals(vapor.internal_energy - liquid.internal_energy, 2),
+        decimals(liquid.enthalpy, 2),
+        decimals(vapor.enthalpy, 2),
+        decimals(vapor.enthalpy - liquid.enthalpy, 2),
+        decimals(liquid.entropy, 5),
+        decimals(vapor.entropy, 5),
+        decimals(vapor.entropy - liquid.entropy, 5),
+    ]
+    .join(",")
+}
+
+const SATURATED_HEADERS: &str = "Specific Volume Liquid (m^3/kg),Specific Volume Vapor (m^3/kg),\
+Internal Energy Liquid (kJ/kg),Internal Energy Vapor (kJ/kg),Internal Energy of Vaporization (kJ/kg),\
+Enthalpy Liquid (kJ/kg),Enthalpy Vapor (kJ/kg),Enthalpy of Vaporization (kJ/kg),\
+Entropy Liquid [kJ/(kg K)],Entropy Vapor [kJ/(kg K)],Entropy of Vaporization [kJ/(kg K)]";
+
+fn saturated_by_temperature(states: &CorrespondingStates) -> String {
+    let mut table = format!("{}\nT (°C),P (MPa),{}\n", PREAMBLE, SATURATED_HEADERS);
+
+    let mut temperatures = vec![HEAVY_WATER_TRIPLE_POINT_TEMPERATURE];
+    temperatures.extend((4..=370).map(f64::from));
+
+    for temperature in temperatures {
+        let kelvin = temperature + KELVIN_OFFSET;
+        table += &format!(
+            "{},{},{}\n",
+            temperature,
+            significant(vapor_pressure(kelvin), 5),
+            saturated_values(states.saturated_states(kelvin))
+        );
+    }
+
+    table += &format!(
+        "{},{},{}\n",
+        HEAVY_WATER_CRITICAL_TEMPERATURE,
+        HEAVY_WATER_CRITICAL_PRESSURE,
+        saturated_values(states.critical_states())
+    );
+
+    table
+}
+
+fn saturated_by_pressure(states: &CorrespondingStates) -> String {
+    let mut table = format!("{}\nP (MPa),T (°C),{}\n", PREAMBLE, SATURATED_HEADERS);
+
+    table += &format!(
+        "{},{},{}\n",
+        HEAVY_WATER_TRIPLE_POINT_PRESSURE,
+        HEAVY_WATER_TRIPLE_POINT_TEMPERATURE,
+        saturated_values(
+            states.saturated_states(HEAVY_WATER_TRIPLE_POINT_TEMPERATURE + KELVIN_OFFSET)
+        )
+    );
+
+    // the pressures of the saturated water table between the triple and critical points of heavy water
+    let pressures = data::SATURATED_BY_PRESSURE_TABLE
+        .lines()
+        .filter_map(|line| line.split(',').next()?.parse::<f64>().ok())
+        .filter(|pressure| {
+            *pressure > HEAVY_WATER_TRIPLE_POINT_PRESSURE
+                && *pressure < HEAVY_WATER_CRITICAL_PRESSURE
+        });
+
+    for pressure in pressures {
+        let temperature = saturation_temperature(pressure);
+        table += &format!(
+            "{},{},{}\n",
+            significant(pressure, 6),
+            decimals(temperature - KELVIN_OFFSET, 3),
+            saturated_values(states.saturated_states(temperature))
+        );
+    }
+
+    table += &format!(
+        "{},{},{}\n",
+        HEAVY_WATER_CRITICAL_PRESSURE,
+        HEAVY_WATER_CRITICAL_TEMPERATURE,
+        saturated_values(states.critical_states())
+    );
+
+    table
+}
+
+fn water_row(state: &State, phase: &str) -> String {
+    format!(
+        "{}, {}, {}, {}, {}, {}, {}, \"{}\"\n",
+        significant(state.pressure, 6),
+        decimals(state.temperature, 3),
+        significant(state.specific_volume, 6),
+        significant(state.specific_volume.recip(), 6),
+        decimals(state.internal_energy, 2),
+        decimals(state.enthalpy, 2),
+        decimals(state.entropy, 5),
+        phase
+    )
+}
+
+fn compressed_liquid_and_superheated_vapor(states: &CorrespondingStates) -> String {
+    let mut table = format!(
+        "{}\nPressure (MPa), Temperature (°C), Specific Volume (m^3/kg), Density (kg/m^3), \
+Specific Internal Energy (kJ/kg), Specific Enthalpy (kJ/kg), Specific Entropy [kJ/(kg K)], Phase\n",
+        PREAMBLE
+    );
+
+    // the temperatures of the water table above the triple point of heavy water
+    let temperatures: Vec<f64> = std::iter::once(HEAVY_WATER_TRIPLE_POINT_TEMPERATURE)
+        .chain((1..=40).map(|index| 5.0 * index as f64))
+        .chain((21..=50).map(|index| 10.0 * index as f64))
+        .chain((26..).map(|index| 20.0 * index as f64))
+        .take_while(|temperature| *temperature <= MAXIMUM_TEMPERATURE)
+        .collect();
+
+    let critical_temperature = HEAVY_WATER_CRITICAL_TEMPERATURE + KELVIN_OFFSET;
+
+    for &pressure in ISOBARS {
+        let saturation =
+            (pressure < HEAVY_WATER_CRITICAL_PRESSURE).then(|| saturation_temperature(pressure));
+        let mut saturation_written = false;
+
+        for temperature in &temperatures {
+            let kelvin = temperature + KELVIN_OFFSET;
+
+            if let Some(saturation) = saturation {
+                if kelvin > saturation && !saturation_written {
+                    let (liquid, vapor) = states.saturated_states(saturation);
+                    let liquid = State { pressure, ..liquid };
+                    let vapor = State { pressure, ..vapor };
+                    table += &water_row(&liquid, "saturated liquid");
+                    table += &water_row(&vapor, "saturated vapor");
+                    saturation_written = true;
+                }
+            }
+
+            let phase = match saturation {
+                Some(saturation) if kelvin < saturation => "liquid",
+                Some(_) => "vapor",
+                None if kelvin > critical_temperature => "supercritical fluid",
+                None => "liquid",
+            };
+
+            let state = states.single_phase_state(pressure, kelvin);
+            table += &water_row(&state, phase);
+        }
+    }
+
+    table
+}
+
+fn main() -> io::Result<()> {
+    let directory = std::env::args()
+        .nth(1)
+        .unwrap_or_else(|| "src/data".to_string());
+    let directory = Path::new(&directory);
+    let states = CorrespondingStates::new();
+
+    fs::write(
+        directory.join("heavy_water_saturated_by_temperature.csv"),
+        saturated_by_temperature(&states),
+    )?;
+    fs::write(
+        directory.join("heavy_water_saturated_by_pressure.csv"),
+        saturated_by_pressure(&states),
+    )?;
+    fs::write(
+        directory.join("heavy_water_compressed_liquid_and_superheated_vapor.csv"),
+        compressed_liquid_and_superheated_vapor(&states),
+    )?;
+
+    Ok(())
+}
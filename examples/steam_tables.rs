@@ -5,7 +5,8 @@ use steam_tables::{data, saturated_steam::SteamTable, water};
 fn main() {
     temperature_table();
     pressure_table();
-    water_table()
+    water_table();
+    turbine_expansion()
 }
 
 fn pressure_table() {
@@ -61,6 +62,26 @@ fn water_table() {
         .unwrap();
     print_data(properties);
 }
+fn turbine_expansion() {
+    println!("Isentropic expansion of steam from 4 MPa, 400 °C to 0.1 MPa");
+    let water_table =
+        water::WaterTable::new(data::COMPRESSED_LIQUID_SUPERHEATED_STEAM.to_string()).unwrap();
+
+    let inlet = water_table.get_state_at_point(4.0, 400.0).unwrap();
+
+    let outlet = water_table
+        .get_values_at_pressure_entropy(0.1, inlet.entropy)
+        .unwrap();
+
+    println!();
+    println!("outlet quality: {:?}", outlet.quality());
+    println!(
+        "isentropic work: {} kJ/kg",
+        inlet.enthalpy - outlet.enthalpy()
+    );
+    println!();
+}
+
 fn print_data<T, K>(data: Vec<(T, K)>)
 where
     T: Display,
@@ -0,0 +1,172 @@
+use crate::error::Error;
+use crate::fluid::Fluid;
+use crate::saturated_steam::{SaturatedProperty, SaturatedState, SteamTable};
+use crate::transport::TransportProperties;
+use crate::water::{derive_by_differences, DerivedProperties, SinglePhaseState, WaterTable};
+
+/// relative pressure step of the differences derived properties are found from by default
+const RELATIVE_PRESSURE_STEP: f64 = 1.0e-4;
+/// temperature step in K of the differences derived properties are found from by default
+const TEMPERATURE_STEP: f64 = 0.01;
+
+/// Source of the properties of saturated water and steam
+///
+/// implemented by the saturated steam tables and by the IAPWS-IF97 formulation,
+/// pressure is in MPa and temperature in °C
+pub trait SaturationProperties {
+    fn saturation_state_at_temperature(&self, temperature: f64) -> Result<SaturatedState, Error>;
+
+    fn saturation_state_at_pressure(&self, pressure: f64) -> Result<SaturatedState, Error>;
+}
+
+/// Source of the properties of compressed liquid water, superheated steam and supercritical fluid
+///
+/// implemented by the water table and by the IAPWS-IF97 formulation,
+/// pressure is in MPa and temperature in °C
+pub trait SinglePhaseProperties {
+    fn single_phase_state(
+        &self,
+        pressure: f64,
+        temperature: f64,
+    ) -> Result<SinglePhaseState, Error>;
+
+    /// Heat capacities, speed of sound, compressibility, expansion and Joule-Thomson coefficients
+    ///
+    /// found by default from central differences of the states around the point
+    fn derived_properties(
+        &self,
+        pressure: f64,
+        temperature: f64,
+    ) -> Result<DerivedProperties, Error> {
+        derive_by_differences(
+            self,
+            pressure,
+            temperature,
+            RELATIVE_PRESSURE_STEP * pressure,
+            TEMPERATURE_STEP,
+        )
+    }
+
+    /// Viscosity, thermal conductivity and Prandtl number of the state in SI units,
+    /// after the IAPWS 2008 and 2011 formulations
+    ///
+    /// the derived properties held by the state are used when the backend computes them
+    /// with the state, so they are only derived once
+    fn transport_properties(
+        &self,
+        pressure: f64,
+        temperature: f64,
+    ) -> Result<TransportProperties, Error> {
+        let state = self.single_phase_state(pressure, temperature)?;
+        let derived_properties = match state.derived_properties {
+            Some(derived_properties) => derived_properties,
+            None => self.derived_properties(pressure, temperature)?,
+        };
+
+        Ok(TransportProperties::new(
+            1.0 / state.specific_volume,
+            temperature,
+            &derived_properties,
+        ))
+    }
+}
+
+impl<T: SaturationProperties + ?Sized> SaturationProperties for &T {
+    fn saturation_state_at_temperature(&self, temperature: f64) -> Result<SaturatedState, Error> {
+        (**self).saturation_state_at_temperature(temperature)
+    }
+
+    fn saturation_state_at_pressure(&self, pressure: f64) -> Result<SaturatedState, Error> {
+        (**self).saturation_state_at_pressure(pressure)
+    }
+}
+
+impl<T: SinglePhaseProperties + ?Sized> SinglePhaseProperties for &T {
+    fn single_phase_state(
+        &self,
+        pressure: f64,
+        temperature: f64,
+    ) -> Result<SinglePhaseState, Error> {
+        (**self).single_phase_state(pressure, temperature)
+    }
+
+    fn derived_properties(
+        &self,
+        pressure: f64,
+        temperature: f64,
+    ) -> Result<DerivedProperties, Error> {
+        (**self).derived_properties(pressure, temperature)
+    }
+
+    fn transport_properties(
+        &self,
+        pressure: f64,
+        temperature: f64,
+    ) -> Result<TransportProperties, Error> {
+        (**self).transport_properties(pressure, temperature)
+    }
+}
+
+impl SaturationProperties for SteamTable {
+    fn saturation_state_at_temperature(&self, temperature: f64) -> Result<SaturatedState, Error> {
+        self.get_state_at_property(SaturatedProperty::Temperature, temperature)
+    }
+
+    fn saturation_state_at_pressure(&self, pressure: f64) -> Result<SaturatedState, Error> {
+        self.get_state_at_property(SaturatedProperty::Pressure, pressure)
+    }
+}
+
+impl SaturationProperties for WaterTable {
+    fn saturation_state_at_temperature(&self, temperature: f64) -> Result<SaturatedState, Error> {
+        self.saturation_table()
+            .saturation_state_at_temperature(temperature)
+    }
+
+    fn saturation_state_at_pressure(&self, pressure: f64) -> Result<SaturatedState, Error> {
+        self.saturation_table()
+            .saturation_state_at_pressure(pressure)
+    }
+}
+
+impl SinglePhaseProperties for WaterTable {
+    fn single_phase_state(
+        &self,
+        pressure: f64,
+        temperature: f64,
+    ) -> Result<SinglePhaseState, Error> {
+        self.get_state_at_point(pressure, temperature)
+    }
+
+    fn derived_properties(
+        &self,
+        pressure: f64,
+        temperature: f64,
+    ) -> Result<DerivedProperties, Error> {
+        self.get_derived_properties_at_point(pressure, temperature)
+    }
+
+    /// the formulations of the transport properties only hold for water, tables of other
+    /// fluids return `Error::UnsupportedFluid`
+    fn transport_properties(
+        &self,
+        pressure: f64,
+        temperature: f64,
+    ) -> Result<TransportProperties, Error> {
+        if self.fluid() != Fluid::Water {
+            return Err(Error::UnsupportedFluid(self.fluid()));
+        }
+
+        let state = self.get_state_with_derived_properties_at_point(pressure, temperature)?;
+        let derived_properties = match state.derived_properties {
+            Some(derived_properties) => derived_properties,
+            None => self.derived_properties(pressure, temperature)?,
+        };
+
+        Ok(TransportProperties::new(
+            1.0 / state.specific_volume,
+            temperature,
+            &derived_properties,
+        ))
+    }
+}
@@ -147,8 +147,8 @@ fn query_water(
         ));
     }
 
-    let pressure = user_input[1].as_str().parse::<f32>();
-    let temperature = user_input[2].as_str().parse::<f32>();
+    let pressure = user_input[1].as_str().parse::<f64>();
+    let temperature = user_input[2].as_str().parse::<f64>();
 
     if pressure.is_err() || temperature.is_err() {
         return Err(InteractiveError::InputError(
@@ -180,7 +180,7 @@ fn query_saturated_steam(
     user_input: Vec<String>,
     table_holder: &TableHolder,
 ) -> Result<(), InteractiveError> {
-    let parameter = user_input[2].parse::<f32>();
+    let parameter = user_input[2].parse::<f64>();
     if parameter.is_err() {
         let err_str = format!("{} can not be parsed to float", user_input[2]);
         return Err(InteractiveError::ParseFloatError(err_str));
@@ -205,7 +205,7 @@ fn query_saturated_steam(
 
 fn query_saturated_steam_at_parameter(
     parameter_name: &str,
-    parameter_value: f32,
+    parameter_value: f64,
     table_holder: &TableHolder,
 ) -> Result<(), InteractiveError> {
     let query_result = if parameter_name == "temperature" {
@@ -0,0 +1,68 @@
+//! Fixed points of water in the units of the tables, where the saturated tables end,
+//! and of heavy water
+
+/// difference between a temperature in K and in °C
+pub const KELVIN_OFFSET: f64 = 273.15;
+
+/// critical temperature in °C
+pub const CRITICAL_TEMPERATURE: f64 = 373.946;
+/// critical temperature in K, used by the IAPWS formulations
+pub const CRITICAL_TEMPERATURE_KELVIN: f64 = CRITICAL_TEMPERATURE + KELVIN_OFFSET;
+/// critical pressure in MPa
+pub const CRITICAL_PRESSURE: f64 = 22.064;
+/// critical density in kg/m^3
+pub const CRITICAL_DENSITY: f64 = 322.0;
+
+/// triple point temperature in °C
+pub const TRIPLE_POINT_TEMPERATURE: f64 = 0.01;
+/// triple point pressure in MPa
+pub const TRIPLE_POINT_PRESSURE: f64 = 0.000611657;
+
+/// critical temperature of heavy water in °C
+pub const HEAVY_WATER_CRITICAL_TEMPERATURE: f64 = 370.697;
+/// critical pressure of heavy water in MPa
+pub const HEAVY_WATER_CRITICAL_PRESSURE: f64 = 21.6618;
+/// critical density of heavy water in kg/m^3
+pub const HEAVY_WATER_CRITICAL_DENSITY: f64 = 356.0;
+
+/// triple point temperature of heavy water in °C
+pub const HEAVY_WATER_TRIPLE_POINT_TEMPERATURE: f64 = 3.819;
+/// triple point pressure of heavy water in MPa
+pub const HEAVY_WATER_TRIPLE_POINT_PRESSURE: f64 = 0.00066159;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data;
+    use crate::saturated_steam::SteamTable;
+
+    #[test]
+    fn test_saturated_tables_end_at_fixed_points() {
+        let temperature_table =
+            SteamTable::new(data::SATURATED_BY_TEMPERATURE_TABLE.to_string()).unwrap();
+        assert_eq!(
+            temperature_table.smallest_valid_point(),
+            TRIPLE_POINT_TEMPERATURE
+        );
+        assert_eq!(
+            temperature_table.largest_valid_point(),
+            CRITICAL_TEMPERATURE
+        );
+
+        let pressure_table =
+            SteamTable::new(data::SATURATED_BY_PRESSURE_TABLE.to_string()).unwrap();
+        assert_eq!(pressure_table.smallest_valid_point(), TRIPLE_POINT_PRESSURE);
+        assert_eq!(pressure_table.largest_valid_point(), CRITICAL_PRESSURE);
+
+        let critical_state = pressure_table
+            .get_state_at_point(CRITICAL_PRESSURE)
+            .unwrap();
+        assert_eq!(critical_state.temperature, CRITICAL_TEMPERATURE);
+        assert_eq!(
+            critical_state.specific_volume_liquid,
+            critical_state.specific_volume_vapor
+        );
+
+        assert!((CRITICAL_TEMPERATURE_KELVIN - 647.096).abs() < 1e-9);
+    }
+}
@@ -2,3 +2,9 @@ pub const SATURATED_BY_TEMPERATURE_TABLE: &str = include_str!("data/saturated_by
 pub const SATURATED_BY_PRESSURE_TABLE: &str = include_str!("data/saturated_by_pressure.csv");
 pub const COMPRESSED_LIQUID_SUPERHEATED_STEAM: &str =
     include_str!("data/compressed_liquid_and_superheated_steam.csv");
+pub const HEAVY_WATER_SATURATED_BY_TEMPERATURE_TABLE: &str =
+    include_str!("data/heavy_water_saturated_by_temperature.csv");
+pub const HEAVY_WATER_SATURATED_BY_PRESSURE_TABLE: &str =
+    include_str!("data/heavy_water_saturated_by_pressure.csv");
+pub const HEAVY_WATER_COMPRESSED_LIQUID_SUPERHEATED_VAPOR: &str =
+    include_str!("data/heavy_water_compressed_liquid_and_superheated_vapor.csv");
@@ -1,5 +1,88 @@
+use std::fmt::Display;
+
+use crate::fluid::Fluid;
+use crate::InterpolationMode;
+
 #[derive(Debug)]
 pub enum Error {
-    ValueOutOfRange(f32, f32),
-    TableParsingError(String),
+    ValueOutOfRange(f64, f64),
+    TableParsingError(ParsingError),
+    NonMonotonicColumn(String),
+    NonConvergence(String),
+    UnsupportedInterpolationMode(InterpolationMode),
+    /// the property is only formulated for water
+    UnsupportedFluid(Fluid),
+    Io(std::io::Error),
 }
+
+/// Location and cause of an error found while parsing a table
+#[derive(Debug, Clone, PartialEq)]
+pub struct ParsingError {
+    /// line of the table starting at 1, if known
+    pub line: Option<usize>,
+    /// column of the table starting at 1, if known
+    pub column: Option<usize>,
+    /// offending text
+    pub text: String,
+    pub message: String,
+}
+
+impl Error {
+    pub(crate) fn table_parsing(
+        line: Option<usize>,
+        column: Option<usize>,
+        text: &str,
+        message: &str,
+    ) -> Error {
+        Error::TableParsingError(ParsingError {
+            line,
+            column,
+            text: text.trim().to_string(),
+            message: message.to_string(),
+        })
+    }
+}
+
+impl Display for ParsingError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if let Some(line) = self.line {
+            write!(f, "line {}, ", line)?;
+        }
+        if let Some(column) = self.column {
+            write!(f, "column {}, ", column)?;
+        }
+        write!(f, "{}: \"{}\"", self.message, self.text)
+    }
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::ValueOutOfRange(min, max) => {
+                write!(
+                    f,
+                    "value out of range, should be between {} and {}",
+                    min, max
+                )
+            }
+            Error::TableParsingError(error) => write!(f, "could not parse table, {}", error),
+            Error::NonMonotonicColumn(header) => {
+                write!(f, "column {} is not monotonic around the value", header)
+            }
+            Error::NonConvergence(message) => write!(f, "{}", message),
+            Error::UnsupportedInterpolationMode(mode) => {
+                write!(
+                    f,
+                    "interpolation mode {:?} is not supported by this table",
+                    mode
+                )
+            }
+            Error::UnsupportedFluid(fluid) => {
+                write!(f, "the property is not available for {}", fluid)
+            }
+            Error::Io(error) => write!(f, "could not read table, {}", error),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
@@ -0,0 +1,168 @@
+//! Free energies and exergy of states in the units of the tables
+
+use crate::backend::SinglePhaseProperties;
+use crate::constants::KELVIN_OFFSET;
+use crate::error::Error;
+use crate::if97::If97;
+use crate::saturated_steam::MixtureState;
+use crate::water::{FlashState, SinglePhaseState};
+
+/// temperature in °C of the default dead state
+pub const STANDARD_TEMPERATURE: f64 = 25.0;
+/// pressure in MPa of the default dead state
+pub const STANDARD_PRESSURE: f64 = 0.101325;
+
+/// specific Gibbs energy g = h - T s in kJ/kg at `temperature` in °C
+pub fn gibbs_energy(enthalpy: f64, entropy: f64, temperature: f64) -> f64 {
+    enthalpy - (temperature + KELVIN_OFFSET) * entropy
+}
+
+/// specific Helmholtz energy a = u - T s in kJ/kg at `temperature` in °C
+pub fn helmholtz_energy(internal_energy: f64, entropy: f64, temperature: f64) -> f64 {
+    internal_energy - (temperature + KELVIN_OFFSET) * entropy
+}
+
+/// Environment state in which water has no exergy
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DeadState {
+    /// °C
+    pub temperature: f64,
+    /// MPa
+    pub pressure: f64,
+    pub specific_volume: f64,
+    pub internal_energy: f64,
+    pub enthalpy: f64,
+    pub entropy: f64,
+}
+
+impl DeadState {
+    /// Dead state at `pressure` and `temperature` with the properties of IAPWS-IF97
+    pub fn new(pressure: f64, temperature: f64) -> Result<DeadState, Error> {
+        DeadState::from_backend(&If97::new(), pressure, temperature)
+    }
+
+    /// Dead state at `pressure` and `temperature` with the properties of `backend`,
+    /// so exergies of its states are zero at the dead state
+    pub fn from_backend<B: SinglePhaseProperties + ?Sized>(
+        backend: &B,
+        pressure: f64,
+        temperature: f64,
+    ) -> Result<DeadState, Error> {
+        let state = backend.single_phase_state(pressure, temperature)?;
+
+        Ok(DeadState {
+            temperature,
+            pressure,
+            specific_volume: state.specific_volume,
+            internal_energy: state.internal_energy,
+            enthalpy: state.enthalpy,
+            entropy: state.entropy,
+        })
+    }
+
+    /// specific flow exergy (h - h0) - T0 (s - s0) in kJ/kg
+    pub fn flow_exergy(&self, enthalpy: f64, entropy: f64) -> f64 {
+        (enthalpy - self.enthalpy) - (self.temperature + KELVIN_OFFSET) * (entropy - self.entropy)
+    }
+
+    /// specific exergy of a closed system (u - u0) + P0 (v - v0) - T0 (s - s0) in kJ/kg
+    pub fn closed_system_exergy(
+        &self,
+        internal_energy: f64,
+        specific_volume: f64,
+        entropy: f64,
+    ) -> f64 {
+        // MPa m^3/kg are 1000 kJ/kg
+        (internal_energy - self.internal_energy)
+            + 1000.0 * self.pressure * (specific_volume - self.specific_volume)
+            - (self.temperature + KELVIN_OFFSET) * (entropy - self.entropy)
+    }
+}
+
+/// liquid water at 25 °C and 0.101325 MPa
+impl Default for DeadState {
+    fn default() -> Self {
+        DeadState::new(STANDARD_PRESSURE, STANDARD_TEMPERATURE)
+            .expect("the standard dead state lies in region 1 of IAPWS-IF97")
+    }
+}
+
+/// Exergy of a state relative to a dead state, states are in the units of the tables
+pub trait Exergy {
+    /// specific flow exergy in kJ/kg, for streams
+    fn flow_exergy(&self, dead_state: &DeadState) -> f64;
+
+    /// specific exergy in kJ/kg of the state held in a closed system
+    fn closed_system_exergy(&self, dead_state: &DeadState) -> f64;
+}
+
+impl Exergy for SinglePhaseState {
+    fn flow_exergy(&self, dead_state: &DeadState) -> f64 {
+        dead_state.flow_exergy(self.enthalpy, self.entropy)
+    }
+
+    fn closed_system_exergy(&self, dead_state: &DeadState) -> f64 {
+        dead_state.closed_system_exergy(self.internal_energy, self.specific_volume, self.entropy)
+    }
+}
+
+impl Exergy for MixtureState {
+    fn flow_exergy(&self, dead_state: &DeadState) -> f64 {
+        dead_state.flow_exergy(self.enthalpy, self.entropy)
+    }
+
+    fn closed_system_exergy(&self, dead_state: &DeadState) -> f64 {
+        dead_state.closed_system_exergy(self.internal_energy, self.specific_volume, self.entropy)
+    }
+}
+
+impl Exergy for FlashState {
+    fn flow_exergy(&self, dead_state: &DeadState) -> f64 {
+        match self {
+            FlashState::SinglePhase(state) => state.flow_exergy(dead_state),
+            FlashState::TwoPhase(mixture) => mixture.flow_exergy(dead_state),
+        }
+    }
+
+    fn closed_system_exergy(&self, dead_state: &DeadState) -> f64 {
+        match self {
+            FlashState::SinglePhase(state) => state.closed_system_exergy(dead_state),
+            FlashState::TwoPhase(mixture) => mixture.closed_system_exergy(dead_state),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data;
+    use crate::water::WaterTable;
+
+    #[test]
+    fn test_dead_state() {
+        let dead_state = DeadState::default();
+        assert_eq!(dead_state.temperature, 25.0);
+        assert!((dead_state.enthalpy - 104.92).abs() < 1e-2);
+        assert!((dead_state.entropy - 0.3672).abs() < 1e-3);
+        assert!(dead_state.flow_exergy(dead_state.enthalpy, dead_state.entropy) == 0.0);
+    }
+
+    #[test]
+    fn test_exergy_of_table_states() {
+        let water_table =
+            WaterTable::new(data::COMPRESSED_LIQUID_SUPERHEATED_STEAM.to_string()).unwrap();
+        let dead_state = DeadState::default();
+
+        // superheated steam at 1 MPa and 300 °C
+        let state = water_table.get_state_at_point(1.0, 300.0).unwrap();
+        let expected = (state.enthalpy - 104.92) - 298.15 * (state.entropy - 0.3672);
+        assert!((state.flow_exergy(&dead_state) - expected).abs() < 0.5);
+        assert!(state.closed_system_exergy(&dead_state) < state.flow_exergy(&dead_state));
+
+        // the dead state taken from the table itself
+        let dead_state = DeadState::from_backend(&water_table, 0.1, 25.0).unwrap();
+        let state = water_table.get_state_at_point(0.1, 25.0).unwrap();
+        assert_eq!(state.flow_exergy(&dead_state), 0.0);
+        assert_eq!(state.closed_system_exergy(&dead_state), 0.0);
+    }
+}
@@ -0,0 +1,153 @@
+//! Fluids the tables can hold, named by the `fluid` line of a table preamble
+
+use std::{fmt::Display, str::FromStr};
+
+use crate::constants::*;
+use crate::data;
+use crate::error::Error;
+use crate::transport;
+
+/// Fluid of a table, tables without a `fluid` line hold water
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum Fluid {
+    /// H2O
+    #[default]
+    Water,
+    /// D2O
+    HeavyWater,
+}
+
+impl Fluid {
+    /// °C
+    pub fn critical_temperature(self) -> f64 {
+        match self {
+            Fluid::Water => CRITICAL_TEMPERATURE,
+            Fluid::HeavyWater => HEAVY_WATER_CRITICAL_TEMPERATURE,
+        }
+    }
+
+    /// MPa
+    pub fn critical_pressure(self) -> f64 {
+        match self {
+            Fluid::Water => CRITICAL_PRESSURE,
+            Fluid::HeavyWater => HEAVY_WATER_CRITICAL_PRESSURE,
+        }
+    }
+
+    /// kg/m^3
+    pub fn critical_density(self) -> f64 {
+        match self {
+            Fluid::Water => CRITICAL_DENSITY,
+            Fluid::HeavyWater => HEAVY_WATER_CRITICAL_DENSITY,
+        }
+    }
+
+    /// °C
+    pub fn triple_point_temperature(self) -> f64 {
+        match self {
+            Fluid::Water => TRIPLE_POINT_TEMPERATURE,
+            Fluid::HeavyWater => HEAVY_WATER_TRIPLE_POINT_TEMPERATURE,
+        }
+    }
+
+    /// MPa
+    pub fn triple_point_pressure(self) -> f64 {
+        match self {
+            Fluid::Water => TRIPLE_POINT_PRESSURE,
+            Fluid::HeavyWater => HEAVY_WATER_TRIPLE_POINT_PRESSURE,
+        }
+    }
+
+    /// Tables bundled with the crate for the fluid
+    ///
+    /// the heavy water tables are mapped from IAPWS-IF97 by corresponding states, see
+    /// `examples/heavy_water_tables.rs`, and are less accurate than those of water
+    pub fn bundled_tables(self) -> BundledTables {
+        match self {
+            Fluid::Water => BundledTables {
+                saturated_by_temperature: data::SATURATED_BY_TEMPERATURE_TABLE,
+                saturated_by_pressure: data::SATURATED_BY_PRESSURE_TABLE,
+                water: data::COMPRESSED_LIQUID_SUPERHEATED_STEAM,
+            },
+            Fluid::HeavyWater => BundledTables {
+                saturated_by_temperature: data::HEAVY_WATER_SATURATED_BY_TEMPERATURE_TABLE,
+                saturated_by_pressure: data::HEAVY_WATER_SATURATED_BY_PRESSURE_TABLE,
+                water: data::HEAVY_WATER_COMPRESSED_LIQUID_SUPERHEATED_VAPOR,
+            },
+        }
+    }
+
+    /// surface tension in N/m against the vapor at the saturation `temperature` in °C
+    pub fn surface_tension(self, temperature: f64) -> Result<f64, Error> {
+        match self {
+            Fluid::Water => transport::surface_tension(temperature),
+            Fluid::HeavyWater => transport::heavy_water_surface_tension(temperature),
+        }
+    }
+}
+
+/// Texts of the tables bundled for a fluid
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BundledTables {
+    pub saturated_by_temperature: &'static str,
+    pub saturated_by_pressure: &'static str,
+    /// compressed liquid and superheated vapor
+    pub water: &'static str,
+}
+
+impl FromStr for Fluid {
+    type Err = Error;
+
+    /// Parses the fluid named in a table preamble, surrounding quotes are ignored
+    fn from_str(token: &str) -> Result<Self, Self::Err> {
+        let fluid = token.trim().trim_matches('"').trim().to_lowercase();
+
+        match fluid.as_str() {
+            "water" | "h2o" => Ok(Fluid::Water),
+            "heavy water" | "d2o" => Ok(Fluid::HeavyWater),
+            _ => Err(Error::table_parsing(None, None, token, "unknown fluid")),
+        }
+    }
+}
+
+impl Display for Fluid {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let name = match self {
+            Fluid::Water => "water",
+            Fluid::HeavyWater => "heavy water",
+        };
+
+        write!(f, "{}", name)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_fluid() {
+        assert_eq!("\"D2O\"".parse::<Fluid>().unwrap(), Fluid::HeavyWater);
+        assert_eq!(" water".parse::<Fluid>().unwrap(), Fluid::Water);
+        assert_eq!(
+            Fluid::HeavyWater.to_string().parse::<Fluid>().unwrap(),
+            Fluid::HeavyWater
+        );
+        assert!("ammonia".parse::<Fluid>().is_err());
+    }
+
+    #[test]
+    fn test_fixed_points() {
+        assert_eq!(Fluid::default().critical_temperature(), 373.946);
+        assert_eq!(Fluid::HeavyWater.critical_pressure(), 21.6618);
+        assert!(
+            Fluid::HeavyWater.triple_point_temperature() > Fluid::Water.triple_point_temperature()
+        );
+        assert_ne!(
+            Fluid::HeavyWater.bundled_tables(),
+            Fluid::Water.bundled_tables()
+        );
+        assert!(Fluid::HeavyWater.surface_tension(1.0).is_err());
+        assert!(Fluid::Water.surface_tension(1.0).is_ok());
+    }
+}
@@ -0,0 +1,308 @@
+//! Ice Ih after IAPWS R10-06 (2009) and its melting and sublimation curves after IAPWS R14-08 (2011)
+//!
+//! takes the units of the tables, pressure in MPa and temperature in °C, with energies
+//! relative to the same reference state, liquid water at the triple point
+
+use crate::backend::SinglePhaseProperties;
+use crate::constants::{self, KELVIN_OFFSET};
+use crate::error::Error;
+use crate::transport::TransportProperties;
+use crate::water::{DerivedProperties, Phase, SinglePhaseState};
+
+mod complex;
+mod gibbs;
+
+use gibbs::IceGibbs;
+
+/// triple point temperature in K
+const TRIPLE_POINT_TEMPERATURE: f64 = constants::TRIPLE_POINT_TEMPERATURE + KELVIN_OFFSET;
+/// triple point pressure in Pa
+const TRIPLE_POINT_PRESSURE: f64 = constants::TRIPLE_POINT_PRESSURE * 1.0e6;
+
+/// highest pressure in MPa of the Gibbs function of ice Ih
+const MAXIMUM_PRESSURE: f64 = 210.0;
+/// lowest temperature in K of the melting curve of ice Ih, the triple point with ice III
+const MINIMUM_MELTING_TEMPERATURE: f64 = 251.165;
+/// lowest temperature in K of the sublimation curve
+const MINIMUM_SUBLIMATION_TEMPERATURE: f64 = 50.0;
+
+/// (a_i, b_i) of the melting pressure of ice Ih
+const MELTING_COEFFICIENTS: [(f64, f64); 3] = [
+    (0.119539337e7, 0.300000e1),
+    (0.808183159e5, 0.257500e2),
+    (0.333826860e4, 0.103750e3),
+];
+
+/// (a_i, b_i) of the sublimation pressure
+const SUBLIMATION_COEFFICIENTS: [(f64, f64); 3] = [
+    (-0.212144006e2, 0.333333333e-2),
+    (0.273203819e2, 0.120666667e1),
+    (-0.610598130e1, 0.170333333e1),
+];
+
+/// melting pressure in MPa of ice Ih at `temperature` in °C, from the triple point
+/// with ice III at -21.985 °C up to the triple point
+pub fn melting_pressure(temperature: f64) -> Result<f64, Error> {
+    let temperature = temperature + KELVIN_OFFSET;
+    if !(MINIMUM_MELTING_TEMPERATURE..=TRIPLE_POINT_TEMPERATURE).contains(&temperature) {
+        return Err(Error::ValueOutOfRange(
+            MINIMUM_MELTING_TEMPERATURE - KELVIN_OFFSET,
+            constants::TRIPLE_POINT_TEMPERATURE,
+        ));
+    }
+
+    let theta = temperature / TRIPLE_POINT_TEMPERATURE;
+    let sum: f64 = MELTING_COEFFICIENTS
+        .iter()
+        .map(|(a, b)| a * (1.0 - theta.powf(*b)))
+        .sum();
+
+    Ok(constants::TRIPLE_POINT_PRESSURE * (1.0 + sum))
+}
+
+/// sublimation pressure in MPa of ice Ih at `temperature` in °C, from -223.15 °C up to the triple point
+pub fn sublimation_pressure(temperature: f64) -> Result<f64, Error> {
+    let temperature = temperature + KELVIN_OFFSET;
+    if !(MINIMUM_SUBLIMATION_TEMPERATURE..=TRIPLE_POINT_TEMPERATURE).contains(&temperature) {
+        return Err(Error::ValueOutOfRange(
+            MINIMUM_SUBLIMATION_TEMPERATURE - KELVIN_OFFSET,
+            constants::TRIPLE_POINT_TEMPERATURE,
+        ));
+    }
+
+    let theta = temperature / TRIPLE_POINT_TEMPERATURE;
+    let sum: f64 = SUBLIMATION_COEFFICIENTS
+        .iter()
+        .map(|(a, b)| a * theta.powf(*b))
+        .sum();
+
+    Ok(constants::TRIPLE_POINT_PRESSURE * (sum / theta).exp())
+}
+
+/// Properties of ice Ih from its Gibbs function, states have the phase `Phase::Solid`
+///
+/// covers temperatures above 0 K up to the triple point and pressures up to 210 MPa,
+/// including ice that is metastable against liquid water or vapor
+#[derive(Debug, Default, Clone, Copy)]
+pub struct Ice;
+
+impl Ice {
+    pub fn new() -> Ice {
+        Ice
+    }
+
+    /// Gibbs energy and its derivatives at `pressure` in MPa and `temperature` in °C
+    fn gibbs(&self, pressure: f64, temperature: f64) -> Result<IceGibbs, Error> {
+        if !(0.0..=MAXIMUM_PRESSURE).contains(&pressure) {
+            return Err(Error::ValueOutOfRange(0.0, MAXIMUM_PRESSURE));
+        }
+        if temperature.is_nan()
+            || temperature <= -KELVIN_OFFSET
+            || temperature > constants::TRIPLE_POINT_TEMPERATURE
+        {
+            return Err(Error::ValueOutOfRange(
+                -KELVIN_OFFSET,
+                constants::TRIPLE_POINT_TEMPERATURE,
+            ));
+        }
+
+        Ok(IceGibbs::new(pressure * 1.0e6, temperature + KELVIN_OFFSET))
+    }
+}
+
+impl SinglePhaseProperties for Ice {
+    fn single_phase_state(
+        &self,
+        pressure: f64,
+        temperature: f64,
+    ) -> Result<SinglePhaseState, Error> {
+        let gibbs = self.gibbs(pressure, temperature)?;
+        let absolute_temperature = temperature + KELVIN_OFFSET;
+        let pressure_pa = pressure * 1.0e6;
+
+        // J/kg to kJ/kg
+        let gibbs_energy = gibbs.g / 1000.0;
+        let entropy = -gibbs.g_t / 1000.0;
+        let enthalpy = gibbs_energy + absolute_temperature * entropy;
+        let internal_energy = enthalpy - pressure_pa * gibbs.g_p / 1000.0;
+
+        let mut state = SinglePhaseState {
+            pressure,
+            temperature,
+            specific_volume: gibbs.g_p,
+            density: 1.0 / gibbs.g_p,
+            internal_energy,
+            enthalpy,
+            entropy,
+            gibbs_energy,
+            helmholtz_energy: internal_energy - absolute_temperature * entropy,
+            phase: Phase::Solid,
+            derived_properties: None,
+        };
+        state.derived_properties = Some(derived_properties(&state, &gibbs));
+
+        Ok(state)
+    }
+
+    fn derived_properties(
+        &self,
+        pressure: f64,
+        temperature: f64,
+    ) -> Result<DerivedProperties, Error> {
+        let state = self.single_phase_state(pressure, temperature)?;
+        let gibbs = self.gibbs(pressure, temperature)?;
+
+        Ok(derived_properties(&state, &gibbs))
+    }
+
+    /// the transport formulations only cover fluid water, from the triple point up
+    fn transport_properties(
+        &self,
+        _pressure: f64,
+        _temperature: f64,
+    ) -> Result<TransportProperties, Error> {
+        Err(Error::ValueOutOfRange(
+            constants::TRIPLE_POINT_TEMPERATURE,
+            f64::INFINITY,
+        ))
+    }
+}
+
+/// Derived properties of ice from the second derivatives of its Gibbs energy
+fn derived_properties(state: &SinglePhaseState, gibbs: &IceGibbs) -> DerivedProperties {
+    DerivedProperties::from_heat_capacity_and_coefficients(
+        state,
+        -(state.temperature + KELVIN_OFFSET) * gibbs.g_tt / 1000.0,
+        // 1/Pa to 1/MPa
+        -gibbs.g_pp / gibbs.g_p * 1.0e6,
+        gibbs.g_tp / gibbs.g_p,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_relative_eq(value: f64, expected: f64, tolerance: f64) {
+        let relative_error = ((value - expected) / expected).abs();
+        assert!(
+            relative_error < tolerance,
+            "{} differs from {} by {}",
+            value,
+            expected,
+            relative_error
+        );
+    }
+
+    #[test]
+    fn test_melting_and_sublimation_pressure() {
+        // (T in K, p in MPa) of the release
+        assert_relative_eq(
+            melting_pressure(260.0 - KELVIN_OFFSET).unwrap(),
+            138.268,
+            1e-5,
+        );
+        assert_relative_eq(
+            sublimation_pressure(230.0 - KELVIN_OFFSET).unwrap(),
+            8.94735e-6,
+            1e-5,
+        );
+
+        // both curves start at the triple point
+        let triple_point = constants::TRIPLE_POINT_TEMPERATURE;
+        assert_relative_eq(
+            melting_pressure(triple_point).unwrap(),
+            constants::TRIPLE_POINT_PRESSURE,
+            1e-12,
+        );
+        assert_relative_eq(
+            sublimation_pressure(triple_point).unwrap(),
+            constants::TRIPLE_POINT_PRESSURE,
+            1e-8,
+        );
+
+        assert!(matches!(
+            melting_pressure(-30.0),
+            Err(Error::ValueOutOfRange(_, _))
+        ));
+        assert!(matches!(
+            sublimation_pressure(5.0),
+            Err(Error::ValueOutOfRange(_, _))
+        ));
+    }
+
+    #[test]
+    fn test_ice_verification_values() {
+        let ice = Ice::new();
+
+        // (p in MPa, T in °C, g in J/kg, ρ in kg/m^3, h in J/kg, s in J/(kg K)) of the release
+        let values = [
+            (
+                0.000611657,
+                0.01,
+                0.611784135,
+                916.709492200,
+                -0.333444253966e6,
+                -0.122069433940e4,
+            ),
+            (
+                0.101325,
+                0.002519,
+                0.101342740690e3,
+                916.721463419,
+                -0.333354873637e6,
+                -0.122076932550e4,
+            ),
+            (
+                100.0,
+                -173.15,
+                -0.222296513088e6,
+                941.678203297,
+                -0.483491635676e6,
+                -0.261195122589e4,
+            ),
+        ];
+
+        for (pressure, temperature, gibbs_energy, density, enthalpy, entropy) in values {
+            let state = ice.single_phase_state(pressure, temperature).unwrap();
+            assert!((state.gibbs_energy * 1000.0 - gibbs_energy).abs() < 1e-5);
+            assert_relative_eq(state.density, density, 1e-9);
+            assert_relative_eq(state.enthalpy * 1000.0, enthalpy, 1e-9);
+            assert_relative_eq(state.entropy * 1000.0, entropy, 1e-9);
+            assert_eq!(state.phase, Phase::Solid);
+        }
+
+        let derived = ice
+            .derived_properties(0.000611657, constants::TRIPLE_POINT_TEMPERATURE)
+            .unwrap();
+        assert_relative_eq(
+            derived.isobaric_heat_capacity * 1000.0,
+            0.209678431622e4,
+            1e-9,
+        );
+        let derived = ice
+            .derived_properties(100.0, 100.0 - KELVIN_OFFSET)
+            .unwrap();
+        assert_relative_eq(
+            derived.isobaric_heat_capacity * 1000.0,
+            0.866333195517e3,
+            1e-9,
+        );
+
+        assert!(ice.single_phase_state(0.1, 5.0).is_err());
+        assert!(ice.single_phase_state(300.0, -5.0).is_err());
+        assert!(ice.transport_properties(0.1, -5.0).is_err());
+    }
+
+    #[test]
+    fn test_heat_of_fusion() {
+        // the liquid of the tables has zero internal energy at the triple point
+        let state = Ice::new()
+            .single_phase_state(
+                constants::TRIPLE_POINT_PRESSURE,
+                constants::TRIPLE_POINT_TEMPERATURE,
+            )
+            .unwrap();
+        assert!((state.internal_energy + 333.4).abs() < 0.1);
+    }
+}
@@ -0,0 +1,96 @@
+//! The few complex operations the Gibbs function of ice Ih needs
+
+use std::ops::{Add, Mul, Sub};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Complex {
+    pub re: f64,
+    pub im: f64,
+}
+
+impl Complex {
+    pub const fn new(re: f64, im: f64) -> Complex {
+        Complex { re, im }
+    }
+
+    /// principal natural logarithm
+    pub fn ln(self) -> Complex {
+        Complex::new(self.re.hypot(self.im).ln(), self.im.atan2(self.re))
+    }
+
+    pub fn recip(self) -> Complex {
+        let norm = self.re * self.re + self.im * self.im;
+        Complex::new(self.re / norm, -self.im / norm)
+    }
+}
+
+impl Add for Complex {
+    type Output = Complex;
+
+    fn add(self, other: Complex) -> Complex {
+        Complex::new(self.re + other.re, self.im + other.im)
+    }
+}
+
+impl Sub for Complex {
+    type Output = Complex;
+
+    fn sub(self, other: Complex) -> Complex {
+        Complex::new(self.re - other.re, self.im - other.im)
+    }
+}
+
+impl Mul for Complex {
+    type Output = Complex;
+
+    fn mul(self, other: Complex) -> Complex {
+        Complex::new(
+            self.re * other.re - self.im * other.im,
+            self.re * other.im + self.im * other.re,
+        )
+    }
+}
+
+impl Add<f64> for Complex {
+    type Output = Complex;
+
+    fn add(self, other: f64) -> Complex {
+        Complex::new(self.re + other, self.im)
+    }
+}
+
+impl Sub<f64> for Complex {
+    type Output = Complex;
+
+    fn sub(self, other: f64) -> Complex {
+        Complex::new(self.re - other, self.im)
+    }
+}
+
+impl Mul<f64> for Complex {
+    type Output = Complex;
+
+    fn mul(self, other: f64) -> Complex {
+        Complex::new(self.re * other, self.im * other)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_complex_arithmetic() {
+        let a = Complex::new(1.0, 2.0);
+        let b = Complex::new(-3.0, 0.5);
+
+        assert_eq!(a * b, Complex::new(-4.0, -5.5));
+        let quotient = a * b * b.recip();
+        assert!((quotient.re - a.re).abs() < 1e-12 && (quotient.im - a.im).abs() < 1e-12);
+
+        let i = Complex::new(0.0, 1.0);
+        let log = i.ln();
+        assert!(log.re.abs() < 1e-15);
+        assert!((log.im - std::f64::consts::FRAC_PI_2).abs() < 1e-15);
+    }
+}
@@ -0,0 +1,93 @@
+//! Gibbs function of ice Ih after IAPWS R10-06 (2009), in SI units
+
+use super::complex::Complex;
+use super::{TRIPLE_POINT_PRESSURE, TRIPLE_POINT_TEMPERATURE};
+
+/// normal pressure in Pa, where the pressure polynomial of the residual entropy is centred
+const NORMAL_PRESSURE: f64 = 101325.0;
+
+/// g0k in J/kg of the pressure polynomial g0(p)
+const G0: [f64; 5] = [
+    -0.632020233335886e6,
+    0.655022213658955,
+    -0.189369929326131e-7,
+    0.339746123271053e-14,
+    -0.556464869058991e-21,
+];
+
+/// residual entropy in J/(kg K), fixing the reference state of IAPWS-95 and the tables
+const S0: f64 = -0.332733756492168e4;
+
+const T1: Complex = Complex::new(0.368017112855051e-1, 0.510878114959572e-1);
+const T2: Complex = Complex::new(0.337315741065416, 0.335449415919309);
+/// r1 in J/(kg K)
+const R1: Complex = Complex::new(0.447050716285388e2, 0.656876847463481e2);
+/// r2k in J/(kg K) of the pressure polynomial r2(p)
+const R2: [Complex; 3] = [
+    Complex::new(-0.725974574329220e2, -0.781008427112870e2),
+    Complex::new(-0.557107698030123e-4, 0.464578634580806e-4),
+    Complex::new(0.234801409215913e-10, -0.285651142904972e-10),
+];
+
+/// Gibbs energy of ice Ih in J/kg and its derivatives with respect to the temperature in K
+/// and the pressure in Pa
+#[derive(Debug, Clone, Copy, Default)]
+pub struct IceGibbs {
+    pub g: f64,
+    pub g_t: f64,
+    pub g_p: f64,
+    pub g_tt: f64,
+    pub g_tp: f64,
+    pub g_pp: f64,
+}
+
+impl IceGibbs {
+    /// Gibbs energy at `pressure` in Pa and `temperature` in K
+    pub fn new(pressure: f64, temperature: f64) -> IceGibbs {
+        let tau = temperature / TRIPLE_POINT_TEMPERATURE;
+        let pi = pressure / TRIPLE_POINT_PRESSURE;
+        let pi_0 = NORMAL_PRESSURE / TRIPLE_POINT_PRESSURE;
+        let delta_pi = pi - pi_0;
+
+        let g0: f64 = (0..5).map(|k| G0[k] * delta_pi.powi(k as i32)).sum();
+        let g0_p: f64 = (1..5)
+            .map(|k| G0[k] * k as f64 * delta_pi.powi(k as i32 - 1))
+            .sum::<f64>()
+            / TRIPLE_POINT_PRESSURE;
+        let g0_pp: f64 = (2..5)
+            .map(|k| G0[k] * (k * (k - 1)) as f64 * delta_pi.powi(k as i32 - 2))
+            .sum::<f64>()
+            / TRIPLE_POINT_PRESSURE.powi(2);
+
+        let r2 = R2[0] + R2[1] * delta_pi + R2[2] * delta_pi.powi(2);
+        let r2_p = (R2[1] + R2[2] * (2.0 * delta_pi)) * (1.0 / TRIPLE_POINT_PRESSURE);
+        let r2_pp = R2[2] * (2.0 / TRIPLE_POINT_PRESSURE.powi(2));
+
+        let (bracket_1, bracket_t_1, bracket_tt_1) = brackets(T1, tau);
+        let (bracket_2, bracket_t_2, bracket_tt_2) = brackets(T2, tau);
+
+        IceGibbs {
+            g: g0 - S0 * TRIPLE_POINT_TEMPERATURE * tau
+                + TRIPLE_POINT_TEMPERATURE * (R1 * bracket_1 + r2 * bracket_2).re,
+            g_t: -S0 + (R1 * bracket_t_1 + r2 * bracket_t_2).re,
+            g_p: g0_p + TRIPLE_POINT_TEMPERATURE * (r2_p * bracket_2).re,
+            g_tt: (R1 * bracket_tt_1 + r2 * bracket_tt_2).re / TRIPLE_POINT_TEMPERATURE,
+            g_tp: (r2_p * bracket_t_2).re,
+            g_pp: g0_pp + TRIPLE_POINT_TEMPERATURE * (r2_pp * bracket_2).re,
+        }
+    }
+}
+
+/// the term of the sum multiplied by r_k at the reduced temperature `tau` and its first
+/// and second derivatives with respect to `tau`
+fn brackets(t: Complex, tau: f64) -> (Complex, Complex, Complex) {
+    let below = t - tau;
+    let above = t + tau;
+
+    let bracket =
+        below * below.ln() + above * above.ln() - t * t.ln() * 2.0 - t.recip() * (tau * tau);
+    let bracket_t = above.ln() - below.ln() - t.recip() * (2.0 * tau);
+    let bracket_tt = below.recip() + above.recip() - t.recip() * 2.0;
+
+    (bracket, bracket_t, bracket_tt)
+}
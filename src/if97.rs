@@ -0,0 +1,674 @@
+use crate::backend::{SaturationProperties, SinglePhaseProperties};
+use crate::constants::{self, CRITICAL_PRESSURE, CRITICAL_TEMPERATURE_KELVIN, KELVIN_OFFSET};
+use crate::error::Error;
+use crate::saturated_steam::SaturatedState;
+use crate::transport::surface_tension;
+use crate::water::{DerivedProperties, Phase, SinglePhaseState};
+
+mod gibbs;
+mod region1;
+mod region2;
+pub(crate) mod region3;
+mod region4;
+mod region5;
+
+/// specific gas constant of water in kJ/(kg K)
+pub const SPECIFIC_GAS_CONSTANT: f64 = 0.461526;
+
+const MINIMUM_TEMPERATURE: f64 = 273.15;
+const MAXIMUM_TEMPERATURE: f64 = 2273.15;
+const MAXIMUM_PRESSURE: f64 = 100.0;
+const MAXIMUM_REGION_5_PRESSURE: f64 = 50.0;
+
+/// temperature in K bounding regions 1 and 3
+const REGION_13_TEMPERATURE: f64 = 623.15;
+/// temperature in K above which region 2 extends up to the maximum pressure
+const REGION_23_MAXIMUM_TEMPERATURE: f64 = 863.15;
+/// temperature in K bounding regions 2 and 5
+const REGION_25_TEMPERATURE: f64 = 1073.15;
+
+/// saturation pressure in MPa at the lowest temperature of the formulation
+const MINIMUM_SATURATION_PRESSURE: f64 = 0.000611212677;
+
+/// Single-phase regions of the IAPWS-IF97 formulation
+///
+/// region 4, the saturation line, is reached through the saturation functions
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Region {
+    /// compressed liquid below 623.15 K
+    Region1,
+    /// superheated steam
+    Region2,
+    /// states around the critical point
+    Region3,
+    /// high temperature steam above 1073.15 K
+    Region5,
+}
+
+/// Properties calculated by IAPWS-IF97
+///
+/// pressure is in MPa, temperature in K, and the remaining properties use the units of the tables
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct If97Properties {
+    pub pressure: f64,
+    pub temperature: f64,
+    pub specific_volume: f64,
+    pub internal_energy: f64,
+    pub enthalpy: f64,
+    pub entropy: f64,
+    /// kJ/(kg K)
+    pub isobaric_heat_capacity: f64,
+    /// kJ/(kg K)
+    pub isochoric_heat_capacity: f64,
+    /// m/s
+    pub speed_of_sound: f64,
+    /// 1/MPa
+    pub isothermal_compressibility: f64,
+    /// 1/K
+    pub volumetric_expansion_coefficient: f64,
+}
+
+impl If97Properties {
+    /// specific Gibbs energy h - T s in kJ/kg
+    pub fn gibbs_energy(&self) -> f64 {
+        self.enthalpy - self.temperature * self.entropy
+    }
+
+    /// specific Helmholtz energy u - T s in kJ/kg
+    pub fn helmholtz_energy(&self) -> f64 {
+        self.internal_energy - self.temperature * self.entropy
+    }
+
+    pub fn derived_properties(&self) -> DerivedProperties {
+        DerivedProperties {
+            isobaric_heat_capacity: self.isobaric_heat_capacity,
+            isochoric_heat_capacity: self.isochoric_heat_capacity,
+            speed_of_sound: self.speed_of_sound,
+            isothermal_compressibility: self.isothermal_compressibility,
+            volumetric_expansion_coefficient: self.volumetric_expansion_coefficient,
+            // MPa m^3/kg are 1000 kJ/kg
+            joule_thomson_coefficient: 1000.0
+                * self.specific_volume
+                * (self.temperature * self.volumetric_expansion_coefficient - 1.0)
+                / self.isobaric_heat_capacity,
+        }
+    }
+}
+
+/// Industrial formulation IAPWS-IF97 for the properties of water and steam
+///
+/// Takes the same units as the tables, pressure in MPa and temperature in °C, and covers
+/// 0 °C to 800 °C up to 100 MPa and 800 °C to 2000 °C up to 50 MPa
+#[derive(Debug, Default, Clone, Copy)]
+pub struct If97;
+
+impl If97 {
+    pub fn new() -> If97 {
+        If97
+    }
+
+    /// Region of the formulation containing the state at `pressure` and `temperature`
+    ///
+    /// states on the saturation line are reported as region 1
+    pub fn region(&self, pressure: f64, temperature: f64) -> Result<Region, Error> {
+        find_region(pressure, temperature + KELVIN_OFFSET)
+    }
+
+    pub fn properties(&self, pressure: f64, temperature: f64) -> Result<If97Properties, Error> {
+        properties(pressure, temperature + KELVIN_OFFSET)
+    }
+
+    /// saturation pressure in MPa at `temperature` in °C
+    pub fn saturation_pressure(&self, temperature: f64) -> Result<f64, Error> {
+        let temperature = temperature + KELVIN_OFFSET;
+        is_saturation_temperature_valid(temperature)?;
+
+        Ok(region4::saturation_pressure(temperature))
+    }
+
+    /// saturation temperature in °C at `pressure` in MPa
+    pub fn saturation_temperature(&self, pressure: f64) -> Result<f64, Error> {
+        is_saturation_pressure_valid(pressure)?;
+
+        Ok(region4::saturation_temperature(pressure) - KELVIN_OFFSET)
+    }
+
+    /// pressure in MPa on the boundary between regions 2 and 3 at `temperature` in °C
+    pub fn boundary_23_pressure(&self, temperature: f64) -> f64 {
+        region4::boundary_23_pressure(temperature + KELVIN_OFFSET)
+    }
+
+    /// temperature in °C on the boundary between regions 2 and 3 at `pressure` in MPa
+    pub fn boundary_23_temperature(&self, pressure: f64) -> f64 {
+        region4::boundary_23_temperature(pressure) - KELVIN_OFFSET
+    }
+
+    /// Properties of saturated liquid and saturated vapor at `temperature` in °C
+    pub fn saturation_properties(
+        &self,
+        temperature: f64,
+    ) -> Result<(If97Properties, If97Properties), Error> {
+        let temperature = temperature + KELVIN_OFFSET;
+        is_saturation_temperature_valid(temperature)?;
+        saturation_properties(temperature)
+    }
+}
+
+impl SinglePhaseProperties for If97 {
+    fn single_phase_state(
+        &self,
+        pressure: f64,
+        temperature: f64,
+    ) -> Result<SinglePhaseState, Error> {
+        let properties = self.properties(pressure, temperature)?;
+
+        Ok(SinglePhaseState {
+            pressure,
+            temperature,
+            specific_volume: properties.specific_volume,
+            density: 1.0 / properties.specific_volume,
+            internal_energy: properties.internal_energy,
+            enthalpy: properties.enthalpy,
+            entropy: properties.entropy,
+            gibbs_energy: properties.gibbs_energy(),
+            helmholtz_energy: properties.helmholtz_energy(),
+            phase: phase_of(properties.pressure, properties.temperature),
+            derived_properties: Some(properties.derived_properties()),
+        })
+    }
+
+    fn derived_properties(
+        &self,
+        pressure: f64,
+        temperature: f64,
+    ) -> Result<DerivedProperties, Error> {
+        Ok(self.properties(pressure, temperature)?.derived_properties())
+    }
+}
+
+impl SaturationProperties for If97 {
+    fn saturation_state_at_temperature(&self, temperature: f64) -> Result<SaturatedState, Error> {
+        let temperature = temperature + KELVIN_OFFSET;
+        is_saturation_temperature_valid(temperature)?;
+
+        let (liquid, vapor) = saturation_properties(temperature)?;
+        Ok(saturated_state(&liquid, &vapor))
+    }
+
+    fn saturation_state_at_pressure(&self, pressure: f64) -> Result<SaturatedState, Error> {
+        is_saturation_pressure_valid(pressure)?;
+
+        let (liquid, vapor) = saturation_properties(region4::saturation_temperature(pressure))?;
+        Ok(saturated_state(&liquid, &vapor))
+    }
+}
+
+fn find_region(pressure: f64, temperature: f64) -> Result<Region, Error> {
+    if !(MINIMUM_TEMPERATURE..=MAXIMUM_TEMPERATURE).contains(&temperature) {
+        return Err(Error::ValueOutOfRange(
+            MINIMUM_TEMPERATURE - KELVIN_OFFSET,
+            MAXIMUM_TEMPERATURE - KELVIN_OFFSET,
+        ));
+    }
+
+    let maximum_pressure = if temperature > REGION_25_TEMPERATURE {
+        MAXIMUM_REGION_5_PRESSURE
+    } else {
+        MAXIMUM_PRESSURE
+    };
+
+    if pressure <= 0.0 || pressure > maximum_pressure {
+        return Err(Error::ValueOutOfRange(0.0, maximum_pressure));
+    }
+
+    let region = if temperature > REGION_25_TEMPERATURE {
+        Region::Region5
+    } else if temperature <= REGION_13_TEMPERATURE {
+        if pressure >= region4::saturation_pressure(temperature) {
+            Region::Region1
+        } else {
+            Region::Region2
+        }
+    } else if temperature <= REGION_23_MAXIMUM_TEMPERATURE
+        && pressure > region4::boundary_23_pressure(temperature)
+    {
+        Region::Region3
+    } else {
+        Region::Region2
+    };
+
+    Ok(region)
+}
+
+fn properties(pressure: f64, temperature: f64) -> Result<If97Properties, Error> {
+    let properties = match find_region(pressure, temperature)? {
+        Region::Region1 => region1::properties(pressure, temperature),
+        Region::Region2 => region2::properties(pressure, temperature),
+        Region::Region3 => {
+            let branch = if temperature < CRITICAL_TEMPERATURE_KELVIN
+                && pressure < region4::saturation_pressure(temperature)
+            {
+                region3::Branch::Vapor
+            } else {
+                region3::Branch::Liquid
+            };
+
+            let density = region3::density(pressure, temperature, branch)?;
+            let mut properties = region3::properties(density, temperature);
+            properties.pressure = pressure;
+            properties
+        }
+        Region::Region5 => region5::properties(pressure, temperature),
+    };
+
+    Ok(properties)
+}
+
+fn saturation_properties(temperature: f64) -> Result<(If97Properties, If97Properties), Error> {
+    let pressure = region4::saturation_pressure(temperature);
+
+    if temperature <= REGION_13_TEMPERATURE {
+        return Ok((
+            region1::properties(pressure, temperature),
+            region2::properties(pressure, temperature),
+        ));
+    }
+
+    let liquid_density = region3::density(pressure, temperature, region3::Branch::Liquid)?;
+    let vapor_density = region3::density(pressure, temperature, region3::Branch::Vapor)?;
+
+    let mut liquid = region3::properties(liquid_density, temperature);
+    let mut vapor = region3::properties(vapor_density, temperature);
+    liquid.pressure = pressure;
+    vapor.pressure = pressure;
+
+    Ok((liquid, vapor))
+}
+
+fn saturated_state(liquid: &If97Properties, vapor: &If97Properties) -> SaturatedState {
+    SaturatedState {
+        temperature: liquid.temperature - KELVIN_OFFSET,
+        pressure: liquid.pressure,
+        specific_volume_liquid: liquid.specific_volume,
+        specific_volume_vapor: vapor.specific_volume,
+        internal_energy_liquid: liquid.internal_energy,
+        internal_energy_vapor: vapor.internal_energy,
+        internal_energy_of_vaporization: vapor.internal_energy - liquid.internal_energy,
+        enthalpy_liquid: liquid.enthalpy,
+        enthalpy_vapor: vapor.enthalpy,
+        enthalpy_of_vaporization: vapor.enthalpy - liquid.enthalpy,
+        entropy_liquid: liquid.entropy,
+        entropy_vapor: vapor.entropy,
+        entropy_of_vaporization: vapor.entropy - liquid.entropy,
+        gibbs_energy_liquid: liquid.gibbs_energy(),
+        gibbs_energy_vapor: vapor.gibbs_energy(),
+        helmholtz_energy_liquid: liquid.helmholtz_energy(),
+        helmholtz_energy_vapor: vapor.helmholtz_energy(),
+        surface_tension: surface_tension(liquid.temperature - KELVIN_OFFSET).ok(),
+    }
+}
+
+/// phase named the way the water table names it
+fn phase_of(pressure: f64, temperature: f64) -> Phase {
+    if temperature >= CRITICAL_TEMPERATURE_KELVIN {
+        if pressure > CRITICAL_PRESSURE {
+            Phase::Supercritical
+        } else {
+            Phase::SuperheatedVapor
+        }
+    } else if pressure >= region4::saturation_pressure(temperature) {
+        Phase::CompressedLiquid
+    } else {
+        Phase::SuperheatedVapor
+    }
+}
+
+fn is_saturation_temperature_valid(temperature: f64) -> Result<(), Error> {
+    if !(MINIMUM_TEMPERATURE..=CRITICAL_TEMPERATURE_KELVIN).contains(&temperature) {
+        return Err(Error::ValueOutOfRange(
+            MINIMUM_TEMPERATURE - KELVIN_OFFSET,
+            constants::CRITICAL_TEMPERATURE,
+        ));
+    }
+
+    Ok(())
+}
+
+fn is_saturation_pressure_valid(pressure: f64) -> Result<(), Error> {
+    if !(MINIMUM_SATURATION_PRESSURE..=CRITICAL_PRESSURE).contains(&pressure) {
+        return Err(Error::ValueOutOfRange(
+            MINIMUM_SATURATION_PRESSURE,
+            CRITICAL_PRESSURE,
+        ));
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data;
+    use crate::saturated_steam::SteamTable;
+    use crate::water::{derive_by_differences, WaterTable};
+
+    fn assert_relative_eq(value: f64, expected: f64, tolerance: f64) {
+        let relative_error = ((value - expected) / expected).abs();
+        assert!(
+            relative_error < tolerance,
+            "{} differs from {} by {}",
+            value,
+            expected,
+            relative_error
+        );
+    }
+
+    /// compares v, h, u, s, cp and w with the verification values of the release
+    fn assert_properties(properties: If97Properties, expected: [f64; 6]) {
+        assert_relative_eq(properties.specific_volume, expected[0], 1e-8);
+        assert_relative_eq(properties.enthalpy, expected[1], 1e-8);
+        assert_relative_eq(properties.internal_energy, expected[2], 1e-8);
+        assert_relative_eq(properties.entropy, expected[3], 1e-8);
+        assert_relative_eq(properties.isobaric_heat_capacity, expected[4], 1e-8);
+        assert_relative_eq(properties.speed_of_sound, expected[5], 1e-8);
+    }
+
+    #[test]
+    fn test_region_1_verification_values() {
+        assert_properties(
+            region1::properties(3.0, 300.0),
+            [
+                0.100215168e-2,
+                0.115331273e3,
+                0.112324818e3,
+                0.392294792,
+                0.417301218e1,
+                0.150773921e4,
+            ],
+        );
+        assert_properties(
+            region1::properties(80.0, 300.0),
+            [
+                0.971180894e-3,
+                0.184142828e3,
+                0.106448356e3,
+                0.368563852,
+                0.401008987e1,
+                0.163469054e4,
+            ],
+        );
+        assert_properties(
+            region1::properties(3.0, 500.0),
+            [
+                0.120241800e-2,
+                0.975542239e3,
+                0.971934985e3,
+                0.258041912e1,
+                0.465580682e1,
+                0.124071337e4,
+            ],
+        );
+    }
+
+    #[test]
+    fn test_region_2_verification_values() {
+        assert_properties(
+            region2::properties(0.0035, 300.0),
+            [
+                0.394913866e2,
+                0.254991145e4,
+                0.241169160e4,
+                0.852238967e1,
+                0.191300162e1,
+                0.427920172e3,
+            ],
+        );
+        assert_properties(
+            region2::properties(0.0035, 700.0),
+            [
+                0.923015898e2,
+                0.333568375e4,
+                0.301262819e4,
+                0.101749996e2,
+                0.208141274e1,
+                0.644289068e3,
+            ],
+        );
+        assert_properties(
+            region2::properties(30.0, 700.0),
+            [
+                0.542946619e-2,
+                0.263149474e4,
+                0.246861076e4,
+                0.517540298e1,
+                0.103505092e2,
+                0.480386523e3,
+            ],
+        );
+    }
+
+    #[test]
+    fn test_region_3_verification_values() {
+        // (T, ρ, p, h, u, s, cp, w)
+        let verification_values = [
+            (
+                650.0,
+                500.0,
+                0.255837018e2,
+                0.186343019e4,
+                0.181226279e4,
+                0.405427273e1,
+                0.138935717e2,
+                0.502005554e3,
+            ),
+            (
+                650.0,
+                200.0,
+                0.222930643e2,
+                0.237512401e4,
+                0.226365868e4,
+                0.485438792e1,
+                0.446579342e2,
+                0.383444594e3,
+            ),
+            (
+                750.0,
+                500.0,
+                0.783095639e2,
+                0.225868845e4,
+                0.210206932e4,
+                0.446971906e1,
+                0.634165359e1,
+                0.760696041e3,
+            ),
+        ];
+
+        for (temperature, density, pressure, h, u, s, cp, w) in verification_values {
+            let properties = region3::properties(density, temperature);
+
+            assert_relative_eq(properties.pressure, pressure, 1e-8);
+            assert_properties(properties, [1.0 / density, h, u, s, cp, w]);
+
+            let branch = if temperature < CRITICAL_TEMPERATURE_KELVIN {
+                region3::Branch::Liquid
+            } else {
+                region3::Branch::Vapor
+            };
+            let calculated_density = region3::density(pressure, temperature, branch).unwrap();
+            assert_relative_eq(calculated_density, density, 1e-7);
+        }
+    }
+
+    #[test]
+    fn test_region_4_verification_values() {
+        assert_relative_eq(region4::saturation_pressure(300.0), 0.353658941e-2, 1e-8);
+        assert_relative_eq(region4::saturation_pressure(500.0), 0.263889776e1, 1e-8);
+        assert_relative_eq(region4::saturation_pressure(600.0), 0.123443146e2, 1e-8);
+
+        assert_relative_eq(region4::saturation_temperature(0.1), 0.372755919e3, 1e-8);
+        assert_relative_eq(region4::saturation_temperature(1.0), 0.453035632e3, 1e-8);
+        assert_relative_eq(region4::saturation_temperature(10.0), 0.584149488e3, 1e-8);
+
+        assert_relative_eq(region4::boundary_23_pressure(623.15), 0.165291643e2, 1e-8);
+        assert_relative_eq(
+            region4::boundary_23_temperature(0.165291643e2),
+            623.15,
+            1e-8,
+        );
+    }
+
+    #[test]
+    fn test_region_5_verification_values() {
+        assert_properties(
+            region5::properties(0.5, 1500.0),
+            [
+                0.138455090e1,
+                0.521976855e4,
+                0.452749310e4,
+                0.965408875e1,
+                0.261609445e1,
+                0.917068690e3,
+            ],
+        );
+        assert_properties(
+            region5::properties(30.0, 1500.0),
+            [
+                0.230761299e-1,
+                0.516723514e4,
+                0.447495124e4,
+                0.772970133e1,
+                0.272724317e1,
+                0.928548002e3,
+            ],
+        );
+        assert_properties(
+            region5::properties(30.0, 2000.0),
+            [
+                0.311385219e-1,
+                0.657122604e4,
+                0.563707038e4,
+                0.853640523e1,
+                0.288569882e1,
+                0.106736948e4,
+            ],
+        );
+    }
+
+    #[test]
+    fn test_derived_properties() {
+        let if97 = If97::new();
+
+        // liquid water at 25 °C and atmospheric pressure
+        let properties = if97.derived_properties(0.101325, 25.0).unwrap();
+        assert_relative_eq(properties.isobaric_heat_capacity, 4.1813, 1e-3);
+        assert_relative_eq(properties.speed_of_sound, 1496.7, 1e-3);
+        assert_relative_eq(properties.isothermal_compressibility, 4.525e-4, 1e-2);
+        assert_relative_eq(properties.volumetric_expansion_coefficient, 2.57e-4, 1e-2);
+        assert!(properties.joule_thomson_coefficient < 0.0);
+
+        let state = if97.single_phase_state(0.101325, 25.0).unwrap();
+        assert_eq!(state.derived_properties, Some(properties));
+
+        // the analytic derivatives agree with differences of the states in every region
+        for (pressure, temperature) in [(3.0, 27.0), (0.0035, 427.0), (25.0, 377.0), (0.5, 1227.0)]
+        {
+            let analytic = if97.derived_properties(pressure, temperature).unwrap();
+            let numeric =
+                derive_by_differences(&if97, pressure, temperature, 1e-4 * pressure, 0.01).unwrap();
+
+            assert_relative_eq(
+                numeric.isobaric_heat_capacity,
+                analytic.isobaric_heat_capacity,
+                1e-4,
+            );
+            assert_relative_eq(
+                numeric.isochoric_heat_capacity,
+                analytic.isochoric_heat_capacity,
+                1e-3,
+            );
+            assert_relative_eq(numeric.speed_of_sound, analytic.speed_of_sound, 1e-3);
+            assert_relative_eq(
+                numeric.isothermal_compressibility,
+                analytic.isothermal_compressibility,
+                1e-4,
+            );
+            assert_relative_eq(
+                numeric.volumetric_expansion_coefficient,
+                analytic.volumetric_expansion_coefficient,
+                1e-4,
+            );
+            assert_relative_eq(
+                numeric.joule_thomson_coefficient,
+                analytic.joule_thomson_coefficient,
+                1e-3,
+            );
+        }
+    }
+
+    #[test]
+    fn test_region() {
+        let if97 = If97::new();
+
+        assert_eq!(if97.region(3.0, 26.85).unwrap(), Region::Region1);
+        assert_eq!(if97.region(0.1, 200.0).unwrap(), Region::Region2);
+        assert_eq!(if97.region(25.0, 370.0).unwrap(), Region::Region3);
+        assert_eq!(if97.region(30.0, 1500.0).unwrap(), Region::Region5);
+
+        assert!(if97.region(60.0, 1500.0).is_err());
+        assert!(if97.region(1.0, -10.0).is_err());
+    }
+
+    #[test]
+    fn test_water_table_against_if97() {
+        let if97 = If97::new();
+        let water_table =
+            WaterTable::new(data::COMPRESSED_LIQUID_SUPERHEATED_STEAM.to_string()).unwrap();
+
+        for (pressure, temperature) in [
+            (0.1, 50.0),
+            (1.0, 300.0),
+            (10.0, 200.0),
+            (20.0, 600.0),
+            (25.0, 380.0),
+            (40.0, 450.0),
+            (5.0, 1000.0),
+        ] {
+            let table_state = water_table
+                .single_phase_state(pressure, temperature)
+                .unwrap();
+            let if97_state = if97.single_phase_state(pressure, temperature).unwrap();
+
+            assert_eq!(table_state.phase, if97_state.phase);
+            assert_relative_eq(
+                table_state.specific_volume,
+                if97_state.specific_volume,
+                1e-3,
+            );
+            assert_relative_eq(table_state.enthalpy, if97_state.enthalpy, 1e-3);
+            assert_relative_eq(table_state.entropy, if97_state.entropy, 1e-3);
+        }
+    }
+
+    #[test]
+    fn test_saturation_tables_against_if97() {
+        let if97 = If97::new();
+        let steam_table =
+            SteamTable::new(data::SATURATED_BY_TEMPERATURE_TABLE.to_string()).unwrap();
+
+        for temperature in [1.0, 50.0, 100.0, 200.0, 300.0, 360.0, 370.0] {
+            let table_state = steam_table
+                .saturation_state_at_temperature(temperature)
+                .unwrap();
+            let if97_state = if97.saturation_state_at_temperature(temperature).unwrap();
+
+            assert_relative_eq(table_state.pressure, if97_state.pressure, 1e-3);
+            assert_relative_eq(
+                table_state.specific_volume_vapor,
+                if97_state.specific_volume_vapor,
+                5e-3,
+            );
+            assert_relative_eq(table_state.enthalpy_vapor, if97_state.enthalpy_vapor, 1e-3);
+            assert_relative_eq(table_state.entropy_liquid, if97_state.entropy_liquid, 5e-3);
+        }
+    }
+}
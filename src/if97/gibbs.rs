@@ -0,0 +1,72 @@
+use super::{If97Properties, SPECIFIC_GAS_CONSTANT};
+
+/// Dimensionless Gibbs free energy γ = g / RT of regions 1, 2 and 5 and its derivatives
+/// with respect to the reduced pressure π and the inverse reduced temperature τ
+#[derive(Debug, Clone, Copy, Default)]
+pub struct GibbsDerivatives {
+    pub gamma: f64,
+    pub gamma_pi: f64,
+    pub gamma_pi_pi: f64,
+    pub gamma_tau: f64,
+    pub gamma_tau_tau: f64,
+    pub gamma_pi_tau: f64,
+}
+
+impl GibbsDerivatives {
+    /// adds the derivatives of a term n π^i τ^j
+    pub fn add_term(&mut self, n: f64, pi: f64, i: i32, tau: f64, j: i32) {
+        let (i_f, j_f) = (i as f64, j as f64);
+
+        self.gamma += n * pi.powi(i) * tau.powi(j);
+        self.gamma_pi += n * i_f * pi.powi(i - 1) * tau.powi(j);
+        self.gamma_pi_pi += n * i_f * (i_f - 1.0) * pi.powi(i - 2) * tau.powi(j);
+        self.gamma_tau += n * pi.powi(i) * j_f * tau.powi(j - 1);
+        self.gamma_tau_tau += n * pi.powi(i) * j_f * (j_f - 1.0) * tau.powi(j - 2);
+        self.gamma_pi_tau += n * i_f * pi.powi(i - 1) * j_f * tau.powi(j - 1);
+    }
+
+    /// adds the ideal-gas part ln π + Σ n τ^j shared by regions 2 and 5
+    pub fn add_ideal_gas_part(&mut self, pi: f64, tau: f64, coefficients: &[(i32, f64)]) {
+        self.gamma += pi.ln();
+        self.gamma_pi += 1.0 / pi;
+        self.gamma_pi_pi += -1.0 / (pi * pi);
+
+        for &(j, n) in coefficients {
+            let j_f = j as f64;
+            self.gamma += n * tau.powi(j);
+            self.gamma_tau += n * j_f * tau.powi(j - 1);
+            self.gamma_tau_tau += n * j_f * (j_f - 1.0) * tau.powi(j - 2);
+        }
+    }
+
+    /// properties at `pressure` in MPa and `temperature` in K
+    pub fn properties(&self, pressure: f64, temperature: f64, pi: f64, tau: f64) -> If97Properties {
+        let rt = SPECIFIC_GAS_CONSTANT * temperature;
+
+        let specific_volume = pi * self.gamma_pi * rt / pressure / 1000.0;
+        let isobaric_heat_capacity = -SPECIFIC_GAS_CONSTANT * tau * tau * self.gamma_tau_tau;
+
+        let a = self.gamma_pi - tau * self.gamma_pi_tau;
+        let isochoric_heat_capacity =
+            SPECIFIC_GAS_CONSTANT * (-tau * tau * self.gamma_tau_tau + a * a / self.gamma_pi_pi);
+
+        let speed_of_sound = (1000.0 * rt * self.gamma_pi * self.gamma_pi
+            / (a * a / (tau * tau * self.gamma_tau_tau) - self.gamma_pi_pi))
+            .sqrt();
+
+        If97Properties {
+            pressure,
+            temperature,
+            specific_volume,
+            internal_energy: rt * (tau * self.gamma_tau - pi * self.gamma_pi),
+            enthalpy: rt * tau * self.gamma_tau,
+            entropy: SPECIFIC_GAS_CONSTANT * (tau * self.gamma_tau - self.gamma),
+            isobaric_heat_capacity,
+            isochoric_heat_capacity,
+            speed_of_sound,
+            isothermal_compressibility: -pi * self.gamma_pi_pi / self.gamma_pi / pressure,
+            volumetric_expansion_coefficient: (1.0 - tau * self.gamma_pi_tau / self.gamma_pi)
+                / temperature,
+        }
+    }
+}
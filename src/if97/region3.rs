@@ -0,0 +1,203 @@
+//! Region 3, water and steam around the critical point described by a Helmholtz free energy
+
+use super::{If97Properties, SPECIFIC_GAS_CONSTANT};
+use crate::constants::{CRITICAL_DENSITY, CRITICAL_TEMPERATURE_KELVIN};
+use crate::error::Error;
+
+const LOGARITHMIC_COEFFICIENT: f64 = 0.10658070028513e1;
+
+/// (I, J, n) coefficients of the dimensionless Helmholtz free energy
+const COEFFICIENTS: [(i32, i32, f64); 39] = [
+    (0, 0, -0.15732845290239e2),
+    (0, 1, 0.20944396974307e2),
+    (0, 2, -0.76867707878716e1),
+    (0, 7, 0.26185947787954e1),
+    (0, 10, -0.28080781148620e1),
+    (0, 12, 0.12053369696517e1),
+    (0, 23, -0.84566812812502e-2),
+    (1, 2, -0.12654315477714e1),
+    (1, 6, -0.11524407806681e1),
+    (1, 15, 0.88521043984318),
+    (1, 17, -0.64207765181607),
+    (2, 0, 0.38493460186671),
+    (2, 2, -0.85214708824206),
+    (2, 6, 0.48972281541877e1),
+    (2, 7, -0.30502617256965e1),
+    (2, 22, 0.39420536879154e-1),
+    (2, 26, 0.12558408424308),
+    (3, 0, -0.27999329698710),
+    (3, 2, 0.13899799569460e1),
+    (3, 4, -0.20189915023570e1),
+    (3, 16, -0.82147637173963e-2),
+    (3, 26, -0.47596035734923),
+    (4, 0, 0.43984074473500e-1),
+    (4, 2, -0.44476435428739),
+    (4, 4, 0.90572070719733),
+    (4, 26, 0.70522450087967),
+    (5, 1, 0.10770512626332),
+    (5, 3, -0.32913623258954),
+    (5, 26, -0.50871062041158),
+    (6, 0, -0.22175400873096e-1),
+    (6, 2, 0.94260751665092e-1),
+    (6, 26, 0.16436278447961),
+    (7, 2, -0.13503372241348e-1),
+    (8, 26, -0.14834345352472e-1),
+    (9, 2, 0.57922953628084e-3),
+    (9, 26, 0.32308904703711e-2),
+    (10, 0, 0.80964802996215e-4),
+    (10, 1, -0.16557679795037e-3),
+    (11, 26, -0.44923899061815e-4),
+];
+
+/// densities in kg/m^3 bounding the searches for the density of region 3,
+/// the pressure at the maximum density is above 100 MPa throughout the region
+const MINIMUM_DENSITY: f64 = 1e-3;
+const MAXIMUM_DENSITY: f64 = 800.0;
+
+const MAXIMUM_ITERATIONS: usize = 200;
+
+/// Branch of the isotherm on which the density is searched
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Branch {
+    Liquid,
+    Vapor,
+}
+
+/// Dimensionless Helmholtz free energy φ = f / RT and its derivatives with respect to
+/// the reduced density δ and the inverse reduced temperature τ
+#[derive(Debug, Clone, Copy, Default)]
+struct HelmholtzDerivatives {
+    phi: f64,
+    phi_delta: f64,
+    phi_delta_delta: f64,
+    phi_tau: f64,
+    phi_tau_tau: f64,
+    phi_delta_tau: f64,
+}
+
+fn helmholtz_derivatives(delta: f64, tau: f64) -> HelmholtzDerivatives {
+    let mut derivatives = HelmholtzDerivatives {
+        phi: LOGARITHMIC_COEFFICIENT * delta.ln(),
+        phi_delta: LOGARITHMIC_COEFFICIENT / delta,
+        phi_delta_delta: -LOGARITHMIC_COEFFICIENT / (delta * delta),
+        ..Default::default()
+    };
+
+    for &(i, j, n) in COEFFICIENTS.iter() {
+        let (i_f, j_f) = (i as f64, j as f64);
+
+        derivatives.phi += n * delta.powi(i) * tau.powi(j);
+        derivatives.phi_delta += n * i_f * delta.powi(i - 1) * tau.powi(j);
+        derivatives.phi_delta_delta += n * i_f * (i_f - 1.0) * delta.powi(i - 2) * tau.powi(j);
+        derivatives.phi_tau += n * delta.powi(i) * j_f * tau.powi(j - 1);
+        derivatives.phi_tau_tau += n * delta.powi(i) * j_f * (j_f - 1.0) * tau.powi(j - 2);
+        derivatives.phi_delta_tau += n * i_f * delta.powi(i - 1) * j_f * tau.powi(j - 1);
+    }
+
+    derivatives
+}
+
+/// properties at `density` in kg/m^3 and `temperature` in K
+pub fn properties(density: f64, temperature: f64) -> If97Properties {
+    let delta = density / CRITICAL_DENSITY;
+    let tau = CRITICAL_TEMPERATURE_KELVIN / temperature;
+    let phi = helmholtz_derivatives(delta, tau);
+
+    let rt = SPECIFIC_GAS_CONSTANT * temperature;
+    let pressure = density * rt * delta * phi.phi_delta / 1000.0;
+
+    let a = delta * phi.phi_delta - delta * tau * phi.phi_delta_tau;
+    let b = 2.0 * delta * phi.phi_delta + delta * delta * phi.phi_delta_delta;
+
+    If97Properties {
+        pressure,
+        temperature,
+        specific_volume: 1.0 / density,
+        internal_energy: rt * tau * phi.phi_tau,
+        enthalpy: rt * (tau * phi.phi_tau + delta * phi.phi_delta),
+        entropy: SPECIFIC_GAS_CONSTANT * (tau * phi.phi_tau - phi.phi),
+        isobaric_heat_capacity: SPECIFIC_GAS_CONSTANT * (-tau * tau * phi.phi_tau_tau + a * a / b),
+        isochoric_heat_capacity: -SPECIFIC_GAS_CONSTANT * tau * tau * phi.phi_tau_tau,
+        speed_of_sound: (1000.0 * rt * (b - a * a / (tau * tau * phi.phi_tau_tau))).sqrt(),
+        // ρRT is in kPa
+        isothermal_compressibility: 1000.0 / (density * rt * b),
+        volumetric_expansion_coefficient: a / b / temperature,
+    }
+}
+
+/// pressure in MPa and its derivative with respect to density at `density` and `temperature`
+fn pressure_and_derivative(density: f64, temperature: f64) -> (f64, f64) {
+    let delta = density / CRITICAL_DENSITY;
+    let tau = CRITICAL_TEMPERATURE_KELVIN / temperature;
+    let phi = helmholtz_derivatives(delta, tau);
+
+    let rt = SPECIFIC_GAS_CONSTANT * temperature;
+    let pressure = density * rt * delta * phi.phi_delta / 1000.0;
+    let derivative =
+        rt * (2.0 * delta * phi.phi_delta + delta * delta * phi.phi_delta_delta) / 1000.0;
+
+    (pressure, derivative)
+}
+
+/// Finds the density in kg/m^3 at `pressure` in MPa and `temperature` in K
+///
+/// Newton iterations are safeguarded by bisection, the search is kept on the requested branch
+/// of the isotherm so that states below the critical temperature never land inside the
+/// unstable part of the van der Waals loop
+pub fn density(pressure: f64, temperature: f64, branch: Branch) -> Result<f64, Error> {
+    let supercritical = temperature >= CRITICAL_TEMPERATURE_KELVIN;
+
+    // bracket [lower, upper] always contains the density on the requested branch
+    let (mut lower, mut upper, mut density) = match branch {
+        Branch::Liquid if !supercritical => (CRITICAL_DENSITY, MAXIMUM_DENSITY, MAXIMUM_DENSITY),
+        Branch::Vapor if !supercritical => {
+            let ideal_gas_density = 1000.0 * pressure / (SPECIFIC_GAS_CONSTANT * temperature);
+            (
+                MINIMUM_DENSITY,
+                CRITICAL_DENSITY,
+                ideal_gas_density.min(CRITICAL_DENSITY),
+            )
+        }
+        _ => (MINIMUM_DENSITY, MAXIMUM_DENSITY, CRITICAL_DENSITY),
+    };
+
+    for _ in 0..MAXIMUM_ITERATIONS {
+        let (calculated_pressure, derivative) = pressure_and_derivative(density, temperature);
+        let residual = calculated_pressure - pressure;
+
+        if residual.abs() < 1e-12 * pressure.max(1.0) {
+            return Ok(density);
+        }
+
+        let below_root = match branch {
+            // the liquid root is the largest density at which the pressure is reached
+            Branch::Liquid => residual < 0.0 || derivative <= 0.0,
+            // the vapor root is the smallest density at which the pressure is reached
+            Branch::Vapor => residual < 0.0 && derivative > 0.0,
+        };
+
+        if below_root {
+            lower = density;
+        } else {
+            upper = density;
+        }
+
+        let newton_density = density - residual / derivative;
+
+        density = if derivative > 0.0 && newton_density > lower && newton_density < upper {
+            newton_density
+        } else {
+            (lower + upper) / 2.0
+        };
+
+        if (upper - lower) < 1e-12 * upper {
+            return Ok(density);
+        }
+    }
+
+    let err_str = format!(
+        "Density of region 3 did not converge at P = {}, T = {}",
+        pressure, temperature
+    );
+    Err(Error::NonConvergence(err_str))
+}
@@ -0,0 +1,61 @@
+//! Region 4, the saturation line, and the boundary between regions 2 and 3
+
+const SATURATION_COEFFICIENTS: [f64; 10] = [
+    0.11670521452767e4,
+    -0.72421316703206e6,
+    -0.17073846940092e2,
+    0.12020824702470e5,
+    -0.32325550322333e7,
+    0.14915108613530e2,
+    -0.48232657361591e4,
+    0.40511340542057e6,
+    -0.23855557567849,
+    0.65017534844798e3,
+];
+
+const B23_COEFFICIENTS: [f64; 5] = [
+    0.34805185628969e3,
+    -0.11671859879975e1,
+    0.10192970039326e-2,
+    0.57254459862746e3,
+    0.13918839778870e2,
+];
+
+/// saturation pressure in MPa at `temperature` in K
+pub fn saturation_pressure(temperature: f64) -> f64 {
+    let n = &SATURATION_COEFFICIENTS;
+
+    let theta = temperature + n[8] / (temperature - n[9]);
+    let a = theta * theta + n[0] * theta + n[1];
+    let b = n[2] * theta * theta + n[3] * theta + n[4];
+    let c = n[5] * theta * theta + n[6] * theta + n[7];
+
+    (2.0 * c / (-b + (b * b - 4.0 * a * c).sqrt())).powi(4)
+}
+
+/// saturation temperature in K at `pressure` in MPa
+pub fn saturation_temperature(pressure: f64) -> f64 {
+    let n = &SATURATION_COEFFICIENTS;
+
+    let beta = pressure.powf(0.25);
+    let e = beta * beta + n[2] * beta + n[5];
+    let f = n[0] * beta * beta + n[3] * beta + n[6];
+    let g = n[1] * beta * beta + n[4] * beta + n[7];
+    let d = 2.0 * g / (-f - (f * f - 4.0 * e * g).sqrt());
+
+    (n[9] + d - ((n[9] + d).powi(2) - 4.0 * (n[8] + n[9] * d)).sqrt()) / 2.0
+}
+
+/// pressure in MPa on the boundary between regions 2 and 3 at `temperature` in K
+pub fn boundary_23_pressure(temperature: f64) -> f64 {
+    let n = &B23_COEFFICIENTS;
+
+    n[0] + n[1] * temperature + n[2] * temperature * temperature
+}
+
+/// temperature in K on the boundary between regions 2 and 3 at `pressure` in MPa
+pub fn boundary_23_temperature(pressure: f64) -> f64 {
+    let n = &B23_COEFFICIENTS;
+
+    n[3] + ((pressure - n[4]) / n[2]).sqrt()
+}
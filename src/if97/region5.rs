@@ -0,0 +1,42 @@
+//! Region 5, high temperature steam between 1073.15 K and 2273.15 K
+
+use super::gibbs::GibbsDerivatives;
+use super::If97Properties;
+
+const REDUCING_PRESSURE: f64 = 1.0;
+const REDUCING_TEMPERATURE: f64 = 1000.0;
+
+/// (J, n) coefficients of the ideal-gas part
+const IDEAL_GAS_COEFFICIENTS: [(i32, f64); 6] = [
+    (0, -0.13179983674201e2),
+    (1, 0.68540841634434e1),
+    (-3, -0.24805148933466e-1),
+    (-2, 0.36901534980333),
+    (-1, -0.31161318213925e1),
+    (2, -0.32961626538917),
+];
+
+/// (I, J, n) coefficients of the residual part
+const RESIDUAL_COEFFICIENTS: [(i32, i32, f64); 6] = [
+    (1, 1, 0.15736404855259e-2),
+    (1, 2, 0.90153761673944e-3),
+    (1, 3, -0.50270077677648e-2),
+    (2, 3, 0.22440037409485e-5),
+    (2, 9, -0.41163275453471e-5),
+    (3, 7, 0.37918836814652e-7),
+];
+
+/// properties at `pressure` in MPa and `temperature` in K
+pub fn properties(pressure: f64, temperature: f64) -> If97Properties {
+    let pi = pressure / REDUCING_PRESSURE;
+    let tau = REDUCING_TEMPERATURE / temperature;
+
+    let mut derivatives = GibbsDerivatives::default();
+    derivatives.add_ideal_gas_part(pi, tau, &IDEAL_GAS_COEFFICIENTS);
+
+    for &(i, j, n) in RESIDUAL_COEFFICIENTS.iter() {
+        derivatives.add_term(n, pi, i, tau, j);
+    }
+
+    derivatives.properties(pressure, temperature, pi, tau)
+}
@@ -1,10 +1,93 @@
+pub mod backend;
+pub mod constants;
 pub mod data;
 pub mod error;
+pub mod exergy;
+pub mod fluid;
+pub mod ice;
+pub mod if97;
+pub mod metadata;
+pub mod regions;
 pub mod saturated_steam;
+pub mod seawater;
+pub mod transport;
+pub mod units;
 pub mod water;
 
 mod math;
 
+pub use math::InterpolationMode;
+
+use std::io::Read;
+
+use error::Error;
+use metadata::TableMetadata;
+
+/// data line of a table paired with its line number starting at 1
+type NumberedLine<'a> = (usize, &'a str);
+
+/// Text of a table split into its preamble, header line and data lines
+struct TableText<'a> {
+    metadata: TableMetadata,
+    /// line number of the headers starting at 1
+    header_line: usize,
+    headers: Vec<String>,
+    lines: Vec<NumberedLine<'a>>,
+}
+
+/// Splits a table into its metadata preamble, headers and non-empty data lines
+///
+/// the data starts at the first line beginning with a number and the headers
+/// are on the last non-empty line before it, so the preamble can have any length
+fn split_table(data_table: &str) -> Result<TableText<'_>, Error> {
+    let numbered_lines: Vec<NumberedLine> = data_table
+        .lines()
+        .enumerate()
+        .map(|(index, line)| (index + 1, line))
+        .filter(|(_, line)| !line.trim().is_empty())
+        .collect();
+
+    let data_start = numbered_lines
+        .iter()
+        .position(|(_, line)| {
+            let first_field = line.split(',').next().unwrap_or_default();
+            first_field.trim().parse::<f64>().is_ok()
+        })
+        .ok_or_else(|| Error::table_parsing(None, None, "", "table has no data lines"))?;
+
+    if data_start == 0 {
+        let (line_number, line) = numbered_lines[0];
+        return Err(Error::table_parsing(
+            Some(line_number),
+            None,
+            line,
+            "table has no header line",
+        ));
+    }
+
+    let (header_line, header_string) = numbered_lines[data_start - 1];
+    let metadata = TableMetadata::parse(
+        numbered_lines[..data_start - 1]
+            .iter()
+            .map(|(_, line)| *line),
+    );
+
+    Ok(TableText {
+        metadata,
+        header_line,
+        headers: get_headers_from_string(header_string),
+        lines: numbered_lines[data_start..].to_vec(),
+    })
+}
+
+/// Reads a whole table from `reader`
+fn read_table(mut reader: impl Read) -> Result<String, Error> {
+    let mut data_table = String::new();
+    reader.read_to_string(&mut data_table).map_err(Error::Io)?;
+
+    Ok(data_table)
+}
+
 fn get_headers_from_string(header_string: &str) -> Vec<String> {
     let headers: Vec<String> = header_string
         .split(',')
@@ -13,3 +96,16 @@ fn get_headers_from_string(header_string: &str) -> Vec<String> {
 
     headers
 }
+
+/// Parses a finite float from the field at `column` of `line`
+fn parse_table_float(field: &str, line: usize, column: usize) -> Result<f64, Error> {
+    match field.trim().parse::<f64>() {
+        Ok(value) if value.is_finite() => Ok(value),
+        _ => Err(Error::table_parsing(
+            Some(line),
+            Some(column),
+            field,
+            "could not parse float",
+        )),
+    }
+}
@@ -1,4 +1,8 @@
-pub fn linear_interpolate(x: f32, point0: Point2, point1: Point2) -> f32 {
+use std::ops::Range;
+
+use crate::constants::KELVIN_OFFSET;
+
+pub fn linear_interpolate(x: f64, point0: Point2, point1: Point2) -> f64 {
     point0.1 + (x - point0.0) * (point1.1 - point0.1) / (point1.0 - point0.0)
 }
 
@@ -14,13 +18,13 @@ pub fn linear_interpolate(x: f32, point0: Point2, point1: Point2) -> f32 {
 ///     |               |               |
 /// point_0_0 ---------- ---------- point_1_0
 pub fn double_linear_interpolate(
-    x: f32,
-    y: f32,
+    x: f64,
+    y: f64,
     point_0_0: Point3,
     point_0_1: Point3,
     point_1_0: Point3,
     point_1_1: Point3,
-) -> f32 {
+) -> f64 {
     // intepolate between point_0_0 and point_0_1 to find point_0_y
     // x remains constant thus only y is intepolated
 
@@ -47,9 +51,326 @@ pub fn double_linear_interpolate(
 }
 
 #[derive(Debug, Clone, Copy)]
-pub struct Point2(pub f32, pub f32);
+pub struct Point2(pub f64, pub f64);
 #[derive(Debug, Clone, Copy)]
-pub struct Point3(pub f32, pub f32, pub f32);
+pub struct Point3(pub f64, pub f64, pub f64);
+
+/// Change of variable applied to a quantity before interpolating and undone afterwards
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Transform {
+    #[default]
+    Identity,
+    /// natural logarithm, for quantities varying exponentially such as saturation pressure
+    Logarithm,
+    /// reciprocal, such as density in place of specific volume
+    Reciprocal,
+    /// reciprocal of the absolute temperature of a temperature in °C
+    ReciprocalAbsoluteTemperature,
+}
+
+impl Transform {
+    pub fn apply(self, value: f64) -> f64 {
+        match self {
+            Transform::Identity => value,
+            Transform::Logarithm => value.ln(),
+            Transform::Reciprocal => 1.0 / value,
+            Transform::ReciprocalAbsoluteTemperature => 1.0 / (value + KELVIN_OFFSET),
+        }
+    }
+
+    pub fn invert(self, value: f64) -> f64 {
+        match self {
+            Transform::Identity => value,
+            Transform::Logarithm => value.exp(),
+            Transform::Reciprocal => 1.0 / value,
+            Transform::ReciprocalAbsoluteTemperature => 1.0 / value - KELVIN_OFFSET,
+        }
+    }
+}
+
+/// Transforms of the point a column is interpolated against and of the column values,
+/// ln P against 1 / T gives the Clausius-Clapeyron relation for the saturation pressure
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct ColumnTransform {
+    pub point: Transform,
+    pub value: Transform,
+}
+
+impl ColumnTransform {
+    pub const IDENTITY: ColumnTransform = ColumnTransform {
+        point: Transform::Identity,
+        value: Transform::Identity,
+    };
+
+    pub fn new(point: Transform, value: Transform) -> ColumnTransform {
+        ColumnTransform { point, value }
+    }
+
+    /// Interpolates like `interpolate` in the transformed variables, falling back to
+    /// the untransformed ones when a transform is undefined for the nodes
+    pub fn interpolate(self, mode: InterpolationMode, x: f64, xs: &[f64], ys: &[f64]) -> f64 {
+        if self == ColumnTransform::IDENTITY {
+            return interpolate(mode, x, xs, ys);
+        }
+
+        // nodes are returned exactly rather than through the round trip of the transform
+        if let Some(index) = xs.iter().position(|node| *node == x) {
+            return ys[index];
+        }
+
+        let transformed_xs: Vec<f64> = xs.iter().map(|x| self.point.apply(*x)).collect();
+        let transformed_ys: Vec<f64> = ys.iter().map(|y| self.value.apply(*y)).collect();
+        let transformed_x = self.point.apply(x);
+
+        let is_defined = transformed_xs
+            .iter()
+            .chain(&transformed_ys)
+            .all(|value| value.is_finite())
+            && transformed_x.is_finite();
+        if !is_defined {
+            return interpolate(mode, x, xs, ys);
+        }
+
+        // the transformed points may decrease, as 1 / T does
+        let (transformed_xs, transformed_ys) = if transformed_xs[0] > transformed_xs[1] {
+            (
+                transformed_xs.into_iter().rev().collect(),
+                transformed_ys.into_iter().rev().collect(),
+            )
+        } else {
+            (transformed_xs, transformed_ys)
+        };
+
+        let value = interpolate(mode, transformed_x, &transformed_xs, &transformed_ys);
+        self.value.invert(value)
+    }
+}
+
+/// Interpolation methods of the tables
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum InterpolationMode {
+    /// straight line between the two nodes around the value
+    #[default]
+    Linear,
+    /// natural cubic spline through a window of nodes around the value
+    NaturalCubicSpline,
+    /// piecewise cubic Hermite interpolation which preserves monotonicity (PCHIP)
+    MonotoneCubic,
+    /// Lagrange polynomial through the given number of nodes nearest to the value,
+    /// there has to be at least two
+    Lagrange(usize),
+    /// cubic convolution (Catmull-Rom) through the four nodes around the value on each axis,
+    /// only tables indexed by two axes support it
+    Bicubic,
+}
+
+/// number of nodes on each side of the cell used by the natural cubic spline
+const SPLINE_HALF_WINDOW: usize = 3;
+
+impl InterpolationMode {
+    /// Range of the nodes needed to interpolate in the cell starting at node `lower`
+    /// of an axis with `length` nodes
+    pub fn window(self, lower: usize, length: usize) -> Range<usize> {
+        let (before, size) = match self {
+            InterpolationMode::Linear => (0, 2),
+            InterpolationMode::NaturalCubicSpline => {
+                (SPLINE_HALF_WINDOW - 1, 2 * SPLINE_HALF_WINDOW)
+            }
+            InterpolationMode::MonotoneCubic | InterpolationMode::Bicubic => (1, 4),
+            InterpolationMode::Lagrange(nodes) => {
+                let nodes = nodes.max(2);
+                ((nodes - 1) / 2, nodes)
+            }
+        };
+
+        let size = size.min(length);
+        let start = lower.saturating_sub(before).min(length - size);
+
+        start..start + size
+    }
+}
+
+/// Interpolates the value at `x` from nodes sorted by increasing `xs`
+///
+/// there has to be at least two nodes, values of `x` outside the nodes are extrapolated
+pub fn interpolate(mode: InterpolationMode, x: f64, xs: &[f64], ys: &[f64]) -> f64 {
+    match mode {
+        InterpolationMode::Linear => {
+            let upper = cell_upper_index(x, xs);
+            linear_interpolate(
+                x,
+                Point2(xs[upper - 1], ys[upper - 1]),
+                Point2(xs[upper], ys[upper]),
+            )
+        }
+        InterpolationMode::NaturalCubicSpline => natural_cubic_spline(x, xs, ys),
+        InterpolationMode::MonotoneCubic => monotone_cubic(x, xs, ys),
+        InterpolationMode::Lagrange(_) => lagrange_polynomial(x, xs, ys),
+        InterpolationMode::Bicubic => cubic_convolution(x, xs, ys),
+    }
+}
+
+/// index of the upper node of the cell holding `x`, clamped to the cells of the nodes
+fn cell_upper_index(x: f64, xs: &[f64]) -> usize {
+    xs.partition_point(|node| *node <= x).clamp(1, xs.len() - 1)
+}
+
+/// Natural cubic spline, the second derivative is zero at the first and last node
+pub fn natural_cubic_spline(x: f64, xs: &[f64], ys: &[f64]) -> f64 {
+    let length = xs.len();
+    if length < 3 {
+        return interpolate(InterpolationMode::Linear, x, xs, ys);
+    }
+
+    let widths: Vec<f64> = xs.windows(2).map(|pair| pair[1] - pair[0]).collect();
+    let slopes: Vec<f64> = (0..length - 1)
+        .map(|index| (ys[index + 1] - ys[index]) / widths[index])
+        .collect();
+
+    // tridiagonal system for the second derivatives of the interior nodes, Thomas algorithm
+    let interior = length - 2;
+    let mut diagonal = vec![0.0; interior];
+    let mut right_side = vec![0.0; interior];
+    for index in 0..interior {
+        diagonal[index] = 2.0 * (widths[index] + widths[index + 1]);
+        right_side[index] = 6.0 * (slopes[index + 1] - slopes[index]);
+    }
+    for index in 1..interior {
+        let factor = widths[index] / diagonal[index - 1];
+        diagonal[index] -= factor * widths[index];
+        right_side[index] -= factor * right_side[index - 1];
+    }
+
+    let mut second_derivatives = vec![0.0; length];
+    for index in (0..interior).rev() {
+        let next = second_derivatives[index + 2];
+        second_derivatives[index + 1] =
+            (right_side[index] - widths[index + 1] * next) / diagonal[index];
+    }
+
+    let upper = cell_upper_index(x, xs);
+    let lower = upper - 1;
+    let width = widths[lower];
+    let (a, b) = ((xs[upper] - x) / width, (x - xs[lower]) / width);
+
+    a * ys[lower]
+        + b * ys[upper]
+        + ((a.powi(3) - a) * second_derivatives[lower]
+            + (b.powi(3) - b) * second_derivatives[upper])
+            * width.powi(2)
+            / 6.0
+}
+
+/// Monotone piecewise cubic Hermite interpolation with the derivatives of Fritsch and Carlson
+pub fn monotone_cubic(x: f64, xs: &[f64], ys: &[f64]) -> f64 {
+    let length = xs.len();
+    if length < 3 {
+        return interpolate(InterpolationMode::Linear, x, xs, ys);
+    }
+
+    let widths: Vec<f64> = xs.windows(2).map(|pair| pair[1] - pair[0]).collect();
+    let slopes: Vec<f64> = (0..length - 1)
+        .map(|index| (ys[index + 1] - ys[index]) / widths[index])
+        .collect();
+
+    let derivative = |index: usize| -> f64 {
+        if index == 0 {
+            return end_derivative(widths[0], widths[1], slopes[0], slopes[1]);
+        }
+        if index == length - 1 {
+            let last = length - 2;
+            return end_derivative(
+                widths[last],
+                widths[last - 1],
+                slopes[last],
+                slopes[last - 1],
+            );
+        }
+
+        let (slope_before, slope_after) = (slopes[index - 1], slopes[index]);
+        if slope_before * slope_after <= 0.0 {
+            return 0.0;
+        }
+
+        // weighted harmonic mean of the slopes
+        let weight_before = 2.0 * widths[index] + widths[index - 1];
+        let weight_after = widths[index] + 2.0 * widths[index - 1];
+        (weight_before + weight_after) / (weight_before / slope_before + weight_after / slope_after)
+    };
+
+    let upper = cell_upper_index(x, xs);
+    hermite(x, xs, ys, upper, derivative(upper - 1), derivative(upper))
+}
+
+/// Cubic convolution, the cubic Hermite interpolation with the derivative at each node
+/// taken from its neighbours (Catmull-Rom), one-sided at the first and last node
+pub fn cubic_convolution(x: f64, xs: &[f64], ys: &[f64]) -> f64 {
+    let length = xs.len();
+    if length < 3 {
+        return interpolate(InterpolationMode::Linear, x, xs, ys);
+    }
+
+    let derivative = |index: usize| -> f64 {
+        let before = index.saturating_sub(1);
+        let after = (index + 1).min(length - 1);
+        (ys[after] - ys[before]) / (xs[after] - xs[before])
+    };
+
+    let upper = cell_upper_index(x, xs);
+    hermite(x, xs, ys, upper, derivative(upper - 1), derivative(upper))
+}
+
+/// Cubic Hermite polynomial on the cell ending at node `upper` with the given node derivatives
+fn hermite(
+    x: f64,
+    xs: &[f64],
+    ys: &[f64],
+    upper: usize,
+    lower_derivative: f64,
+    upper_derivative: f64,
+) -> f64 {
+    let lower = upper - 1;
+    let width = xs[upper] - xs[lower];
+    let t = (x - xs[lower]) / width;
+
+    let h00 = (1.0 + 2.0 * t) * (1.0 - t).powi(2);
+    let h10 = t * (1.0 - t).powi(2);
+    let h01 = t.powi(2) * (3.0 - 2.0 * t);
+    let h11 = t.powi(2) * (t - 1.0);
+
+    h00 * ys[lower]
+        + h10 * width * lower_derivative
+        + h01 * ys[upper]
+        + h11 * width * upper_derivative
+}
+
+/// Derivative at an end node from the three point formula, limited to keep monotonicity
+fn end_derivative(width: f64, next_width: f64, slope: f64, next_slope: f64) -> f64 {
+    let derivative =
+        ((2.0 * width + next_width) * slope - width * next_slope) / (width + next_width);
+
+    if derivative * slope <= 0.0 {
+        0.0
+    } else if slope * next_slope <= 0.0 && derivative.abs() > 3.0 * slope.abs() {
+        3.0 * slope
+    } else {
+        derivative
+    }
+}
+
+/// Lagrange polynomial through all the nodes
+pub fn lagrange_polynomial(x: f64, xs: &[f64], ys: &[f64]) -> f64 {
+    (0..xs.len())
+        .map(|index| {
+            let basis: f64 = (0..xs.len())
+                .filter(|other| *other != index)
+                .map(|other| (x - xs[other]) / (xs[index] - xs[other]))
+                .product();
+
+            basis * ys[index]
+        })
+        .sum()
+}
 
 #[cfg(test)]
 mod tests {
@@ -87,4 +408,89 @@ mod tests {
             u
         );
     }
+
+    #[test]
+    fn test_interpolation_window() {
+        assert_eq!(InterpolationMode::Linear.window(3, 10), 3..5);
+        assert_eq!(InterpolationMode::MonotoneCubic.window(3, 10), 2..6);
+        assert_eq!(InterpolationMode::MonotoneCubic.window(0, 10), 0..4);
+        assert_eq!(InterpolationMode::MonotoneCubic.window(8, 10), 6..10);
+        assert_eq!(InterpolationMode::Bicubic.window(3, 10), 2..6);
+        assert_eq!(InterpolationMode::NaturalCubicSpline.window(3, 10), 1..7);
+        assert_eq!(InterpolationMode::NaturalCubicSpline.window(1, 4), 0..4);
+        assert_eq!(InterpolationMode::Lagrange(3).window(3, 10), 2..5);
+        assert_eq!(InterpolationMode::Lagrange(4).window(3, 10), 2..6);
+    }
+
+    #[test]
+    fn test_higher_order_interpolation() {
+        let xs = [0.0, 1.0, 2.5, 3.0, 4.0];
+        let line: Vec<f64> = xs.iter().map(|x| 2.0 * x + 1.0).collect();
+        let cubic: Vec<f64> = xs.iter().map(|x| x * x * x - 2.0 * x).collect();
+
+        for mode in [
+            InterpolationMode::Linear,
+            InterpolationMode::NaturalCubicSpline,
+            InterpolationMode::MonotoneCubic,
+            InterpolationMode::Lagrange(5),
+            InterpolationMode::Bicubic,
+        ] {
+            assert!((interpolate(mode, 1.7, &xs, &line) - 4.4).abs() < 1e-5);
+            assert!((interpolate(mode, 2.5, &xs, &cubic) - cubic[2]).abs() < 1e-5);
+        }
+
+        let expected = 1.7f64.powi(3) - 3.4;
+        assert!((lagrange_polynomial(1.7, &xs[..4], &cubic[..4]) - expected).abs() < 1e-4);
+    }
+
+    #[test]
+    fn test_column_transform() {
+        // ln P against 1 / T is a straight line for this saturation pressure
+        let saturation_pressure =
+            |temperature: f64| 1.0e3 * (-4000.0 / (temperature + 273.15)).exp();
+        let temperatures = [100.0, 120.0];
+        let pressures = temperatures.map(saturation_pressure);
+
+        let clausius_clapeyron = ColumnTransform::new(
+            Transform::ReciprocalAbsoluteTemperature,
+            Transform::Logarithm,
+        );
+        let pressure = clausius_clapeyron.interpolate(
+            InterpolationMode::Linear,
+            110.0,
+            &temperatures,
+            &pressures,
+        );
+        assert!((pressure / saturation_pressure(110.0) - 1.0).abs() < 1e-5);
+
+        let linear_pressure = ColumnTransform::IDENTITY.interpolate(
+            InterpolationMode::Linear,
+            110.0,
+            &temperatures,
+            &pressures,
+        );
+        assert!((linear_pressure / saturation_pressure(110.0) - 1.0).abs() > 1e-3);
+
+        // the logarithm of a negative value falls back to linear interpolation
+        let logarithm = ColumnTransform::new(Transform::Identity, Transform::Logarithm);
+        let value =
+            logarithm.interpolate(InterpolationMode::Linear, 0.5, &[0.0, 1.0], &[-1.0, 1.0]);
+        assert_eq!(value, 0.0);
+    }
+
+    #[test]
+    fn test_monotone_cubic_preserves_monotonicity() {
+        let xs = [0.0, 1.0, 2.0, 3.0, 4.0];
+        let ys = [0.0, 0.0, 0.1, 10.0, 10.0];
+
+        let mut previous = 0.0;
+        for step in 0..=40 {
+            let value = monotone_cubic(step as f64 * 0.1, &xs, &ys);
+            assert!(value >= previous - 1e-6 && value <= 10.0 + 1e-6);
+            previous = value;
+        }
+
+        // the natural spline overshoots on the same data
+        assert!(natural_cubic_spline(3.5, &xs, &ys) > 10.0);
+    }
 }
@@ -0,0 +1,78 @@
+use crate::fluid::Fluid;
+
+/// Metadata found in the preamble of a table, before its header line
+///
+/// every preamble line is a `key, value` pair, keys other than the known ones are kept in `other`
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct TableMetadata {
+    /// fluid named by a `fluid` line, tables without one hold water
+    pub fluid: Option<Fluid>,
+    pub version: Option<String>,
+    pub author: Option<String>,
+    pub copyright: Option<String>,
+    pub date: Option<String>,
+    pub license: Option<String>,
+    pub other: Vec<(String, String)>,
+}
+
+impl TableMetadata {
+    /// Parses the preamble lines of a table, lines without a comma are ignored
+    pub fn parse<'a>(lines: impl IntoIterator<Item = &'a str>) -> TableMetadata {
+        let mut metadata = TableMetadata::default();
+
+        for line in lines {
+            let Some((key, value)) = line.split_once(',') else {
+                continue;
+            };
+
+            let key = key.trim().trim_start_matches('\u{feff}').to_lowercase();
+            let value = value.trim().trim_matches('"').to_string();
+
+            match key.as_str() {
+                "fluid" => match value.parse() {
+                    Ok(fluid) => metadata.fluid = Some(fluid),
+                    Err(_) => metadata.other.push((key, value)),
+                },
+                "version" => metadata.version = Some(value),
+                "author" => metadata.author = Some(value),
+                "copyright" => metadata.copyright = Some(value),
+                "date" => metadata.date = Some(value),
+                "license" => metadata.license = Some(value),
+                _ => metadata.other.push((key, value)),
+            }
+        }
+
+        metadata
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_metadata() {
+        let lines = [
+            "\u{feff}version,1.4",
+            "author, Neil Hendren",
+            "license,\"Permission is hereby granted, free of charge\"",
+            "source, vendor",
+            "fluid, D2O",
+            "",
+        ];
+
+        let metadata = TableMetadata::parse(lines);
+        assert_eq!(metadata.version.as_deref(), Some("1.4"));
+        assert_eq!(metadata.author.as_deref(), Some("Neil Hendren"));
+        assert_eq!(
+            metadata.license.as_deref(),
+            Some("Permission is hereby granted, free of charge")
+        );
+        assert_eq!(metadata.date, None);
+        assert_eq!(metadata.fluid, Some(Fluid::HeavyWater));
+        assert_eq!(
+            metadata.other,
+            vec![("source".to_string(), "vendor".to_string())]
+        );
+    }
+}
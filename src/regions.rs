@@ -0,0 +1,162 @@
+//! Saturation line and phase regions of water from the saturated steam tables and the
+//! melting and sublimation curves of ice, to classify a state before querying the water table
+
+use std::sync::OnceLock;
+
+use crate::constants::{CRITICAL_PRESSURE, CRITICAL_TEMPERATURE, TRIPLE_POINT_TEMPERATURE};
+use crate::data;
+use crate::error::Error;
+use crate::ice;
+use crate::saturated_steam::SteamTable;
+use crate::water::Phase;
+
+static SATURATED_BY_TEMPERATURE: OnceLock<SteamTable> = OnceLock::new();
+static SATURATED_BY_PRESSURE: OnceLock<SteamTable> = OnceLock::new();
+
+/// the bundled saturated table indexed by temperature, parsed on first use
+fn saturated_by_temperature() -> &'static SteamTable {
+    SATURATED_BY_TEMPERATURE.get_or_init(|| {
+        SteamTable::new(data::SATURATED_BY_TEMPERATURE_TABLE.to_string())
+            .expect("the bundled saturated table by temperature parses")
+    })
+}
+
+/// the bundled saturated table indexed by pressure, parsed on first use
+fn saturated_by_pressure() -> &'static SteamTable {
+    SATURATED_BY_PRESSURE.get_or_init(|| {
+        SteamTable::new(data::SATURATED_BY_PRESSURE_TABLE.to_string())
+            .expect("the bundled saturated table by pressure parses")
+    })
+}
+
+/// saturation temperature in °C at `pressure` in MPa, between the triple and the critical point
+pub fn saturation_temperature(pressure: f64) -> Result<f64, Error> {
+    let table = saturated_by_pressure();
+    if pressure.is_nan() {
+        return Err(Error::ValueOutOfRange(
+            table.smallest_valid_point(),
+            table.largest_valid_point(),
+        ));
+    }
+
+    Ok(table.get_state_at_point(pressure)?.temperature)
+}
+
+/// saturation pressure in MPa at `temperature` in °C, between the triple and the critical point
+pub fn saturation_pressure(temperature: f64) -> Result<f64, Error> {
+    let table = saturated_by_temperature();
+    if temperature.is_nan() {
+        return Err(Error::ValueOutOfRange(
+            table.smallest_valid_point(),
+            table.largest_valid_point(),
+        ));
+    }
+
+    Ok(table.get_state_at_point(temperature)?.pressure)
+}
+
+/// whether `pressure` in MPa and `temperature` in °C are both above the critical point
+pub fn is_supercritical(pressure: f64, temperature: f64) -> bool {
+    pressure > CRITICAL_PRESSURE && temperature >= CRITICAL_TEMPERATURE
+}
+
+/// Phase of water at `pressure` in MPa and `temperature` in °C named the way the water table names it
+///
+/// water at its saturation pressure counts as compressed liquid like in the water table,
+/// below the triple point ice is bounded by its sublimation and melting curves,
+/// down to the lowest temperature of the sublimation curve, -223.15 °C
+pub fn region_of(pressure: f64, temperature: f64) -> Result<Phase, Error> {
+    if pressure.is_nan() || pressure < 0.0 {
+        return Err(Error::ValueOutOfRange(0.0, f64::INFINITY));
+    }
+
+    if temperature < TRIPLE_POINT_TEMPERATURE {
+        if pressure < ice::sublimation_pressure(temperature)? {
+            return Ok(Phase::SuperheatedVapor);
+        }
+
+        // below the triple point of ice Ih, ice III and liquid, water is solid at any pressure
+        return match ice::melting_pressure(temperature) {
+            Ok(melting_pressure) if pressure > melting_pressure => Ok(Phase::CompressedLiquid),
+            _ => Ok(Phase::Solid),
+        };
+    }
+
+    if temperature >= CRITICAL_TEMPERATURE {
+        if is_supercritical(pressure, temperature) {
+            Ok(Phase::Supercritical)
+        } else {
+            Ok(Phase::SuperheatedVapor)
+        }
+    } else if pressure >= saturation_pressure(temperature)? {
+        Ok(Phase::CompressedLiquid)
+    } else {
+        Ok(Phase::SuperheatedVapor)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::constants::TRIPLE_POINT_PRESSURE;
+    use crate::data;
+    use crate::water::WaterTable;
+
+    #[test]
+    fn test_saturation_line() {
+        assert_eq!(saturation_temperature(1.0).unwrap(), 179.878);
+        assert_eq!(saturation_pressure(100.0).unwrap(), 0.10142);
+        assert_eq!(
+            saturation_temperature(CRITICAL_PRESSURE).unwrap(),
+            CRITICAL_TEMPERATURE
+        );
+        assert_eq!(
+            saturation_temperature(TRIPLE_POINT_PRESSURE).unwrap(),
+            TRIPLE_POINT_TEMPERATURE
+        );
+
+        // the two tables about agree between their rows
+        let temperature = saturation_temperature(2.5).unwrap();
+        assert!((saturation_pressure(temperature).unwrap() - 2.5).abs() < 2e-3);
+
+        assert!(matches!(
+            saturation_pressure(400.0),
+            Err(Error::ValueOutOfRange(_, _))
+        ));
+        assert!(matches!(
+            saturation_temperature(f64::NAN),
+            Err(Error::ValueOutOfRange(_, _))
+        ));
+    }
+
+    #[test]
+    fn test_region_of() {
+        assert_eq!(region_of(0.1, 25.0).unwrap(), Phase::CompressedLiquid);
+        assert_eq!(region_of(0.1, 150.0).unwrap(), Phase::SuperheatedVapor);
+        assert_eq!(region_of(30.0, 200.0).unwrap(), Phase::CompressedLiquid);
+        assert_eq!(region_of(30.0, 400.0).unwrap(), Phase::Supercritical);
+        assert_eq!(region_of(10.0, 400.0).unwrap(), Phase::SuperheatedVapor);
+        assert!(is_supercritical(30.0, 400.0));
+        assert!(!is_supercritical(30.0, 200.0));
+        assert!(region_of(-1.0, 25.0).is_err());
+        assert!(region_of(0.1, -250.0).is_err());
+
+        // ice between its sublimation and melting curves
+        assert_eq!(region_of(0.1, -5.0).unwrap(), Phase::Solid);
+        assert_eq!(region_of(0.1, 0.0).unwrap(), Phase::Solid);
+        assert_eq!(region_of(0.1, 0.005).unwrap(), Phase::CompressedLiquid);
+        assert_eq!(region_of(1.0e-4, -5.0).unwrap(), Phase::SuperheatedVapor);
+        assert_eq!(region_of(150.0, -10.0).unwrap(), Phase::CompressedLiquid);
+        assert_eq!(region_of(150.0, -30.0).unwrap(), Phase::Solid);
+
+        // the regions agree with the phases of the water table
+        let water_table =
+            WaterTable::new(data::COMPRESSED_LIQUID_SUPERHEATED_STEAM.to_string()).unwrap();
+        for (pressure, temperature) in [(0.1, 25.0), (1.0, 300.0), (5.0, 200.0), (30.0, 500.0)] {
+            let state = water_table
+                .get_state_at_point(pressure, temperature)
+                .unwrap();
+            assert_eq!(region_of(pressure, temperature).unwrap(), state.phase);
+        }
+    }
+}
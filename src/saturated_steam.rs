@@ -1,49 +1,481 @@
+use std::{fs::File, io::Read, path::Path};
+
 use crate::error::Error;
+use crate::exergy::{gibbs_energy, helmholtz_energy};
+use crate::fluid::Fluid;
+use crate::if97::If97;
+use crate::math::*;
+use crate::metadata::TableMetadata;
+use crate::transport::TransportProperties;
+pub use datapoint::EstimatedDataPoint;
 use datapoint::*;
+pub use mixture::{MixtureProperty, MixtureState};
+pub use state::{SaturatedProperty, SaturatedState};
 
 mod datapoint;
+mod mixture;
+mod state;
+
+/// iterations of the search for the row at which a column equals a value
+const MAXIMUM_ITERATIONS: usize = 100;
 
 pub struct SteamTable {
+    metadata: TableMetadata,
     headers: Vec<String>,
+    /// column of each `SaturatedProperty`, found from the headers
+    columns: Vec<usize>,
     datapoints: Vec<DataPoint>,
+    /// transform of each value column applied when interpolating
+    transforms: Vec<ColumnTransform>,
+    interpolation_mode: InterpolationMode,
 }
 
 impl SteamTable {
     pub fn new(data_table: String) -> Result<SteamTable, Error> {
-        let data_lines: Vec<&str> = data_table.lines().collect();
+        let table_text = super::split_table(&data_table)?;
+        let headers = table_text.headers;
+        let columns = state::find_property_columns(&headers, table_text.header_line)?;
 
-        let headers: Vec<String> = super::get_headers_from_string(data_lines[6]);
-        let str_data: Vec<String> = data_lines[7..].iter().map(|str| str.to_string()).collect();
-
-        let mut datapoints = parse_to_datapoint_struct(str_data)?;
-        datapoints.sort_by(|a, b| a.point.partial_cmp(&b.point).unwrap());
+        let mut datapoints = parse_to_datapoint_struct(table_text.lines, headers.len())?;
+        datapoints.sort_by(|a, b| a.point.total_cmp(&b.point));
+        // the bundled tables repeat some rows
+        datapoints.dedup_by(|a, b| a.point == b.point);
+        let transforms = state::find_column_transforms(&columns, headers.len() - 1);
 
         let steam_table = SteamTable {
+            metadata: table_text.metadata,
             headers,
+            columns,
             datapoints,
+            transforms,
+            interpolation_mode: InterpolationMode::Linear,
         };
 
         Ok(steam_table)
     }
 
-    pub fn get_values_at_point(&self, point: f32) -> Result<DataPoint, Error> {
+    /// Reads a saturated steam table from the file at `path`
+    pub fn from_path(path: impl AsRef<Path>) -> Result<SteamTable, Error> {
+        let file = File::open(path).map_err(Error::Io)?;
+        SteamTable::from_reader(file)
+    }
+
+    pub fn from_reader(reader: impl Read) -> Result<SteamTable, Error> {
+        SteamTable::new(super::read_table(reader)?)
+    }
+
+    /// metadata from the preamble of the table
+    pub fn metadata(&self) -> &TableMetadata {
+        &self.metadata
+    }
+
+    /// fluid named by the preamble of the table, water by default
+    pub fn fluid(&self) -> Fluid {
+        self.metadata.fluid.unwrap_or_default()
+    }
+
+    /// the saturated table by temperature bundled for `fluid`
+    pub fn bundled_by_temperature(fluid: Fluid) -> Result<SteamTable, Error> {
+        SteamTable::new(fluid.bundled_tables().saturated_by_temperature.to_string())
+    }
+
+    /// the saturated table by pressure bundled for `fluid`
+    pub fn bundled_by_pressure(fluid: Fluid) -> Result<SteamTable, Error> {
+        SteamTable::new(fluid.bundled_tables().saturated_by_pressure.to_string())
+    }
+
+    pub fn interpolation_mode(&self) -> InterpolationMode {
+        self.interpolation_mode
+    }
+
+    /// Sets how values between the rows of the table are interpolated, `Linear` by default
+    ///
+    /// `Bicubic` needs a second axis and `Lagrange` less than two nodes is not a polynomial,
+    /// both are rejected
+    pub fn set_interpolation_mode(
+        &mut self,
+        interpolation_mode: InterpolationMode,
+    ) -> Result<(), Error> {
+        match interpolation_mode {
+            InterpolationMode::Bicubic | InterpolationMode::Lagrange(0..=1) => {
+                Err(Error::UnsupportedInterpolationMode(interpolation_mode))
+            }
+            _ => {
+                self.interpolation_mode = interpolation_mode;
+                Ok(())
+            }
+        }
+    }
+
+    pub fn get_values_at_point(&self, point: f64) -> Result<DataPoint, Error> {
         self.is_point_valid(point)?;
 
+        if self.interpolation_mode != InterpolationMode::Linear && self.datapoints.len() > 1 {
+            return Ok(self.interpolate_with_mode(self.interpolation_mode, point));
+        }
+
         let (min_data_point, max_data_point) = self.get_bounding_points(point);
 
         Ok(interpolate_data_points(
             point,
             min_data_point,
             max_data_point,
+            &self.transforms,
         ))
     }
 
-    pub fn get_labelled_values_at_point(&self, point: f32) -> Result<Vec<(String, f32)>, Error> {
+    /// Interpolates like `get_values_at_point`, together with the rows around the query
+    /// and an estimate of the interpolation error of every column
+    pub fn get_estimated_values_at_point(&self, point: f64) -> Result<EstimatedDataPoint, Error> {
+        let data_point = self.get_values_at_point(point)?;
+
+        if self.datapoints.len() < 2 {
+            return Ok(EstimatedDataPoint {
+                data_point,
+                bounds: (point, point),
+                cell_size: 0.0,
+                errors: None,
+            });
+        }
+
+        let upper = upper_row_index(&self.datapoints, point);
+        let bounds = (
+            self.datapoints[upper - 1].point,
+            self.datapoints[upper].point,
+        );
+
+        // the difference between linear and quadratic interpolation estimates the error
+        let errors = (self.datapoints.len() > 2).then(|| {
+            let linear = self.interpolate_with_mode(InterpolationMode::Linear, point);
+            let quadratic = self.interpolate_with_mode(InterpolationMode::Lagrange(3), point);
+
+            linear
+                .values
+                .iter()
+                .zip(&quadratic.values)
+                .map(|(linear, quadratic)| (quadratic - linear).abs())
+                .collect()
+        });
+
+        Ok(EstimatedDataPoint {
+            data_point,
+            bounds,
+            cell_size: bounds.1 - bounds.0,
+            errors,
+        })
+    }
+
+    pub fn get_labelled_values_at_point(&self, point: f64) -> Result<Vec<(String, f64)>, Error> {
         let data_point = self.get_values_at_point(point)?;
         Ok(self.merge_header_with_data_point(data_point))
     }
 
-    fn get_bounding_points(&self, point: f32) -> (DataPoint, DataPoint) {
+    pub fn get_state_at_point(&self, point: f64) -> Result<SaturatedState, Error> {
+        let data_point = self.get_values_at_point(point)?;
+        Ok(self.state_from_data_point(&data_point))
+    }
+
+    /// Finds the saturation state at which the column of `property` equals `value`
+    ///
+    /// Any column can be searched as long as it is monotonic over the range containing `value`,
+    /// if the value occurs at more than one point of the table `Error::NonMonotonicColumn` is returned
+    pub fn get_values_at_property(
+        &self,
+        property: SaturatedProperty,
+        value: f64,
+    ) -> Result<DataPoint, Error> {
+        let column = self.column_index(property);
+
+        let (smallest_value, largest_value) = self.datapoints.iter().fold(
+            (f64::INFINITY, f64::NEG_INFINITY),
+            |(smallest, largest), datapoint| {
+                let column_value = datapoint.column_value(column);
+                (smallest.min(column_value), largest.max(column_value))
+            },
+        );
+
+        // NaN fails every comparison, so it is rejected here rather than matching nothing
+        if !value.is_finite() || value < smallest_value || value > largest_value {
+            return Err(Error::ValueOutOfRange(smallest_value, largest_value));
+        }
+
+        // the index column is looked up directly rather than searched
+        if column == 0 {
+            return self.get_values_at_point(value);
+        }
+
+        // segments holding the value as (lower point, upper point) with the linear estimate of its point
+        let mut matching_segments: Vec<((f64, f64), f64)> = Vec::new();
+
+        for (lower, upper) in self.datapoints.iter().zip(self.datapoints.iter().skip(1)) {
+            let lower_value = lower.column_value(column);
+            let upper_value = upper.column_value(column);
+
+            if value < lower_value.min(upper_value) || value > lower_value.max(upper_value) {
+                continue;
+            }
+
+            let point = if lower_value == upper_value {
+                lower.point
+            } else {
+                linear_interpolate(
+                    value,
+                    Point2(lower_value, lower.point),
+                    Point2(upper_value, upper.point),
+                )
+            };
+
+            // a value on a row lies in the segments on both sides of it
+            if !matching_segments.iter().any(|(_, found)| *found == point) {
+                matching_segments.push(((lower.point, upper.point), point));
+            }
+        }
+
+        let mut data_point = match matching_segments.as_slice() {
+            [(segment, estimate)] => self.solve_for_column(column, value, *segment, *estimate)?,
+            // a table of a single row has no segments to search
+            [] => {
+                let point = self
+                    .datapoints
+                    .iter()
+                    .find(|datapoint| datapoint.column_value(column) == value)
+                    .map(|datapoint| datapoint.point)
+                    .ok_or(Error::ValueOutOfRange(smallest_value, largest_value))?;
+                self.get_values_at_point(point)?
+            }
+            _ => return Err(Error::NonMonotonicColumn(self.headers[column].clone())),
+        };
+
+        // the search stops within rounding of the value, which is returned as requested
+        data_point.values[column - 1] = value;
+
+        Ok(data_point)
+    }
+
+    /// Interpolated row within `segment` at which `column` equals `value`
+    ///
+    /// the column is interpolated through `get_values_at_point` with the transforms and
+    /// the interpolation mode of the table, so the row is searched by regula falsi starting
+    /// from the linear `estimate`, which is kept when the segment does not bracket the value
+    fn solve_for_column(
+        &self,
+        column: usize,
+        value: f64,
+        segment: (f64, f64),
+        estimate: f64,
+    ) -> Result<DataPoint, Error> {
+        let residual = |point: f64| -> Result<(DataPoint, f64), Error> {
+            let data_point = self.get_values_at_point(point)?;
+            let residual = data_point.column_value(column) - value;
+            Ok((data_point, residual))
+        };
+
+        let (mut data_point, estimate_residual) = residual(estimate)?;
+        let (mut lower, mut lower_residual) = (segment.0, residual(segment.0)?.1);
+        let (mut upper, mut upper_residual) = (segment.1, residual(segment.1)?.1);
+
+        if estimate_residual == 0.0 || lower_residual.signum() == upper_residual.signum() {
+            return Ok(data_point);
+        }
+
+        // the estimate replaces the end of the segment on its side of the value
+        if estimate_residual.signum() == upper_residual.signum() {
+            (upper, upper_residual) = (estimate, estimate_residual);
+        } else {
+            (lower, lower_residual) = (estimate, estimate_residual);
+        }
+
+        let tolerance = 4.0 * f64::EPSILON * value.abs().max(1.0);
+
+        for _ in 0..MAXIMUM_ITERATIONS {
+            let point =
+                upper - upper_residual * (upper - lower) / (upper_residual - lower_residual);
+            if point <= lower.min(upper) || point >= lower.max(upper) {
+                break;
+            }
+
+            let (found, found_residual) = residual(point)?;
+            data_point = found;
+            if found_residual.abs() <= tolerance {
+                break;
+            }
+
+            // the Illinois modification halves the residual of an end kept twice in a row
+            if found_residual.signum() == upper_residual.signum() {
+                lower_residual /= 2.0;
+            } else {
+                (lower, lower_residual) = (upper, upper_residual);
+            }
+            (upper, upper_residual) = (point, found_residual);
+        }
+
+        Ok(data_point)
+    }
+
+    pub fn get_labelled_values_at_property(
+        &self,
+        property: SaturatedProperty,
+        value: f64,
+    ) -> Result<Vec<(String, f64)>, Error> {
+        let data_point = self.get_values_at_property(property, value)?;
+        Ok(self.merge_header_with_data_point(data_point))
+    }
+
+    pub fn get_state_at_property(
+        &self,
+        property: SaturatedProperty,
+        value: f64,
+    ) -> Result<SaturatedState, Error> {
+        let data_point = self.get_values_at_property(property, value)?;
+        Ok(self.state_from_data_point(&data_point))
+    }
+
+    fn state_from_data_point(&self, data_point: &DataPoint) -> SaturatedState {
+        let value =
+            |property: SaturatedProperty| data_point.column_value(self.column_index(property));
+
+        let temperature = value(SaturatedProperty::Temperature);
+
+        SaturatedState {
+            temperature,
+            pressure: value(SaturatedProperty::Pressure),
+            specific_volume_liquid: value(SaturatedProperty::SpecificVolumeLiquid),
+            specific_volume_vapor: value(SaturatedProperty::SpecificVolumeVapor),
+            internal_energy_liquid: value(SaturatedProperty::InternalEnergyLiquid),
+            internal_energy_vapor: value(SaturatedProperty::InternalEnergyVapor),
+            internal_energy_of_vaporization: value(SaturatedProperty::InternalEnergyOfVaporization),
+            enthalpy_liquid: value(SaturatedProperty::EnthalpyLiquid),
+            enthalpy_vapor: value(SaturatedProperty::EnthalpyVapor),
+            enthalpy_of_vaporization: value(SaturatedProperty::EnthalpyOfVaporization),
+            entropy_liquid: value(SaturatedProperty::EntropyLiquid),
+            entropy_vapor: value(SaturatedProperty::EntropyVapor),
+            entropy_of_vaporization: value(SaturatedProperty::EntropyOfVaporization),
+            gibbs_energy_liquid: gibbs_energy(
+                value(SaturatedProperty::EnthalpyLiquid),
+                value(SaturatedProperty::EntropyLiquid),
+                temperature,
+            ),
+            gibbs_energy_vapor: gibbs_energy(
+                value(SaturatedProperty::EnthalpyVapor),
+                value(SaturatedProperty::EntropyVapor),
+                temperature,
+            ),
+            helmholtz_energy_liquid: helmholtz_energy(
+                value(SaturatedProperty::InternalEnergyLiquid),
+                value(SaturatedProperty::EntropyLiquid),
+                temperature,
+            ),
+            helmholtz_energy_vapor: helmholtz_energy(
+                value(SaturatedProperty::InternalEnergyVapor),
+                value(SaturatedProperty::EntropyVapor),
+                temperature,
+            ),
+            surface_tension: self.fluid().surface_tension(temperature).ok(),
+        }
+    }
+
+    /// Transport properties of saturated liquid and saturated vapor at the saturation point
+    /// `point` as (liquid, vapor)
+    ///
+    /// the densities come from the table and the heat capacities of the thermal
+    /// conductivity from IAPWS-IF97, so tables of other fluids than water return
+    /// `Error::UnsupportedFluid`
+    pub fn get_transport_properties_at_point(
+        &self,
+        point: f64,
+    ) -> Result<(TransportProperties, TransportProperties), Error> {
+        if self.fluid() != Fluid::Water {
+            return Err(Error::UnsupportedFluid(self.fluid()));
+        }
+
+        let state = self.get_state_at_point(point)?;
+        let (liquid, vapor) = If97::new().saturation_properties(state.temperature)?;
+
+        Ok((
+            TransportProperties::new(
+                1.0 / state.specific_volume_liquid,
+                state.temperature,
+                &liquid.derived_properties(),
+            ),
+            TransportProperties::new(
+                1.0 / state.specific_volume_vapor,
+                state.temperature,
+                &vapor.derived_properties(),
+            ),
+        ))
+    }
+
+    /// Properties of wet steam of the given `quality` at the saturation point `point`
+    pub fn get_mixture_at_quality(&self, point: f64, quality: f64) -> Result<MixtureState, Error> {
+        if !(0.0..=1.0).contains(&quality) {
+            return Err(Error::ValueOutOfRange(0.0, 1.0));
+        }
+
+        let data_point = self.get_values_at_point(point)?;
+        Ok(self.mixture_from_data_point(&data_point, quality))
+    }
+
+    /// Properties of wet steam at the saturation point `point` whose `property` equals `value`
+    ///
+    /// returns `Error::ValueOutOfRange` with the saturated liquid and vapor values
+    /// when `value` lies outside of the two-phase region
+    pub fn get_mixture_at_property(
+        &self,
+        point: f64,
+        property: MixtureProperty,
+        value: f64,
+    ) -> Result<MixtureState, Error> {
+        let data_point = self.get_values_at_point(point)?;
+
+        let (liquid_property, vapor_property) = property.saturated_properties();
+        let liquid_value = data_point.column_value(self.column_index(liquid_property));
+        let vapor_value = data_point.column_value(self.column_index(vapor_property));
+
+        let quality = mixture::quality_from_value(value, liquid_value, vapor_value)?;
+
+        Ok(self.mixture_from_data_point(&data_point, quality))
+    }
+
+    fn mixture_from_data_point(&self, data_point: &DataPoint, quality: f64) -> MixtureState {
+        let mix_property = |property: MixtureProperty| {
+            let (liquid_property, vapor_property) = property.saturated_properties();
+
+            mixture::mix_values(
+                data_point.column_value(self.column_index(liquid_property)),
+                data_point.column_value(self.column_index(vapor_property)),
+                quality,
+            )
+        };
+
+        let temperature =
+            data_point.column_value(self.column_index(SaturatedProperty::Temperature));
+        let internal_energy = mix_property(MixtureProperty::InternalEnergy);
+        let enthalpy = mix_property(MixtureProperty::Enthalpy);
+        let entropy = mix_property(MixtureProperty::Entropy);
+
+        MixtureState {
+            temperature,
+            pressure: data_point.column_value(self.column_index(SaturatedProperty::Pressure)),
+            quality,
+            specific_volume: mix_property(MixtureProperty::SpecificVolume),
+            internal_energy,
+            enthalpy,
+            entropy,
+            gibbs_energy: gibbs_energy(enthalpy, entropy, temperature),
+            helmholtz_energy: helmholtz_energy(internal_energy, entropy, temperature),
+        }
+    }
+
+    /// true when the table is indexed by temperature, false when indexed by pressure
+    pub fn is_temperature_indexed(&self) -> bool {
+        self.column_index(SaturatedProperty::Temperature) == 0
+    }
+
+    fn column_index(&self, property: SaturatedProperty) -> usize {
+        self.columns[property as usize]
+    }
+
+    fn get_bounding_points(&self, point: f64) -> (DataPoint, DataPoint) {
         let mut lower_bound = self.smallest_valid_point();
         let mut upper_bound = self.largest_valid_point();
 
@@ -63,7 +495,27 @@ impl SteamTable {
         (lower_point.unwrap(), upper_point.unwrap())
     }
 
-    fn get_data_point(&self, point: f32) -> Option<DataPoint> {
+    /// Interpolates every column at `point` through the rows needed by `mode`
+    fn interpolate_with_mode(&self, mode: InterpolationMode, point: f64) -> DataPoint {
+        let upper = upper_row_index(&self.datapoints, point);
+        let window = &self.datapoints[mode.window(upper - 1, self.datapoints.len())];
+
+        let points: Vec<f64> = window.iter().map(|datapoint| datapoint.point).collect();
+        let values = (0..window[0].values.len())
+            .map(|column| {
+                let column_values: Vec<f64> = window
+                    .iter()
+                    .map(|datapoint| datapoint.values[column])
+                    .collect();
+
+                self.transforms[column].interpolate(mode, point, &points, &column_values)
+            })
+            .collect();
+
+        DataPoint { point, values }
+    }
+
+    fn get_data_point(&self, point: f64) -> Option<DataPoint> {
         let mut data_point = self
             .datapoints
             .iter()
@@ -72,12 +524,12 @@ impl SteamTable {
         data_point.next().cloned()
     }
 
-    fn merge_header_with_data_point(&self, data_point: DataPoint) -> Vec<(String, f32)> {
-        let mut labelled_data: Vec<(String, f32)> = Vec::new();
+    fn merge_header_with_data_point(&self, data_point: DataPoint) -> Vec<(String, f64)> {
+        let mut labelled_data: Vec<(String, f64)> = Vec::new();
 
         labelled_data.push((self.headers[0].clone(), data_point.point));
 
-        let mut tail_data: Vec<(String, f32)> = self.headers[1..]
+        let mut tail_data: Vec<(String, f64)> = self.headers[1..]
             .iter()
             .zip(data_point.values)
             .map(|pair| (pair.0.clone(), pair.1))
@@ -88,24 +540,456 @@ impl SteamTable {
         labelled_data
     }
 
-    pub fn is_point_valid(&self, point: f32) -> Result<(), Error> {
+    pub fn is_point_valid(&self, point: f64) -> Result<(), Error> {
         let smallest_point = self.smallest_valid_point();
         let largest_point = self.largest_valid_point();
 
-        if point < smallest_point || point > largest_point {
+        if !(smallest_point..=largest_point).contains(&point) {
             return Err(Error::ValueOutOfRange(smallest_point, largest_point));
         }
 
         Ok(())
     }
 
-    pub fn smallest_valid_point(&self) -> f32 {
+    pub fn smallest_valid_point(&self) -> f64 {
         self.datapoints[0].point
     }
 
-    pub fn largest_valid_point(&self) -> f32 {
+    pub fn largest_valid_point(&self) -> f64 {
         let datapoints_length = self.datapoints.len();
 
         self.datapoints[datapoints_length - 1].point
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::constants::*;
+    use crate::data;
+    use crate::transport::surface_tension;
+
+    #[test]
+    fn test_mixture_energy_balance() {
+        // h = u + P v with P in MPa and v in m³/kg giving kJ/kg
+        for table in [
+            data::SATURATED_BY_TEMPERATURE_TABLE,
+            data::SATURATED_BY_PRESSURE_TABLE,
+        ] {
+            let steam_table = SteamTable::new(table.to_string()).unwrap();
+
+            for data_point in &steam_table.datapoints {
+                for quality in [0.0, 0.3, 0.7, 1.0] {
+                    let mixture = steam_table
+                        .get_mixture_at_quality(data_point.point, quality)
+                        .unwrap();
+                    let flow_work = 1.0e3 * mixture.pressure * mixture.specific_volume;
+                    let imbalance = mixture.enthalpy - mixture.internal_energy - flow_work;
+                    assert!(imbalance.abs() < 0.1, "{:?}", mixture);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_get_values_at_property() {
+        let steam_table =
+            SteamTable::new(data::SATURATED_BY_TEMPERATURE_TABLE.to_string()).unwrap();
+
+        let data_point = steam_table
+            .get_values_at_property(SaturatedProperty::EntropyVapor, 7.3541)
+            .unwrap();
+        assert!((data_point.point - 100.0).abs() < 1e-3);
+
+        let data_point = steam_table
+            .get_values_at_property(SaturatedProperty::Pressure, 0.10142)
+            .unwrap();
+        assert!((data_point.point - 100.0).abs() < 1e-3);
+    }
+
+    #[test]
+    fn test_get_values_at_property_on_pressure_table() {
+        let steam_table = SteamTable::new(data::SATURATED_BY_PRESSURE_TABLE.to_string()).unwrap();
+
+        let data_point = steam_table
+            .get_values_at_property(SaturatedProperty::Temperature, 179.878)
+            .unwrap();
+        assert!((data_point.point - 1.0).abs() < 1e-4);
+    }
+
+    #[test]
+    fn test_get_values_at_property_round_trip() {
+        let pressure_table =
+            SteamTable::new(data::SATURATED_BY_PRESSURE_TABLE.to_string()).unwrap();
+        let mut temperature_table =
+            SteamTable::new(data::SATURATED_BY_TEMPERATURE_TABLE.to_string()).unwrap();
+
+        // the point found has to give back the value through the transforms of the table
+        let data_point = pressure_table
+            .get_values_at_property(SaturatedProperty::Temperature, 150.0)
+            .unwrap();
+        let temperature_column = pressure_table.column_index(SaturatedProperty::Temperature);
+        assert_eq!(data_point.column_value(temperature_column), 150.0);
+        let state = pressure_table.get_state_at_point(data_point.point).unwrap();
+        assert!((state.temperature - 150.0).abs() < 1e-9);
+
+        for mode in [InterpolationMode::Linear, InterpolationMode::MonotoneCubic] {
+            temperature_table.set_interpolation_mode(mode).unwrap();
+
+            let state = temperature_table
+                .get_state_at_property(SaturatedProperty::Pressure, 0.5)
+                .unwrap();
+            assert_eq!(state.pressure, 0.5);
+            let round_trip = temperature_table
+                .get_state_at_point(state.temperature)
+                .unwrap();
+            assert!((round_trip.pressure - 0.5).abs() < 1e-12);
+            assert!((round_trip.enthalpy_vapor - state.enthalpy_vapor).abs() < 1e-9);
+        }
+    }
+
+    #[test]
+    fn test_get_mixture_at_quality() {
+        let steam_table =
+            SteamTable::new(data::SATURATED_BY_TEMPERATURE_TABLE.to_string()).unwrap();
+
+        let mixture = steam_table.get_mixture_at_quality(100.0, 0.5).unwrap();
+        assert_eq!(mixture.temperature, 100.0);
+        assert_eq!(mixture.pressure, 0.10142);
+        assert!((mixture.enthalpy - (419.17 + 0.5 * (2675.6 - 419.17))).abs() < 1e-9);
+        assert!(
+            (mixture.specific_volume - (0.00104346 + 0.5 * (1.6718 - 0.00104346))).abs() < 1e-12
+        );
+
+        // the triple point pressure keeps all of its digits
+        let pressure_table =
+            SteamTable::new(data::SATURATED_BY_PRESSURE_TABLE.to_string()).unwrap();
+        let state = pressure_table.get_state_at_point(0.000611657).unwrap();
+        assert_eq!(state.pressure, 0.000611657);
+        let mixture = pressure_table
+            .get_mixture_at_quality(0.000611657, 0.25)
+            .unwrap();
+        assert!((mixture.enthalpy - 0.25 * 2500.9).abs() < 1e-9);
+
+        assert!(steam_table.get_mixture_at_quality(100.0, 1.5).is_err());
+    }
+
+    #[test]
+    fn test_get_mixture_at_property() {
+        let steam_table = SteamTable::new(data::SATURATED_BY_PRESSURE_TABLE.to_string()).unwrap();
+
+        let mixture = steam_table
+            .get_mixture_at_property(1.0, MixtureProperty::Entropy, 4.3615)
+            .unwrap();
+        assert_eq!(mixture.pressure, 1.0);
+        assert_eq!(mixture.temperature, 179.878);
+        assert!((mixture.quality - 0.5).abs() < 1e-4);
+
+        assert!(matches!(
+            steam_table.get_mixture_at_property(1.0, MixtureProperty::Enthalpy, 3000.0),
+            Err(Error::ValueOutOfRange(_, _))
+        ));
+        assert!(matches!(
+            steam_table.get_mixture_at_property(1.0, MixtureProperty::Enthalpy, f64::NAN),
+            Err(Error::ValueOutOfRange(_, _))
+        ));
+        assert!(matches!(
+            steam_table.get_mixture_at_quality(1.0, f64::NAN),
+            Err(Error::ValueOutOfRange(_, _))
+        ));
+    }
+
+    #[test]
+    fn test_get_state_at_point() {
+        let steam_table =
+            SteamTable::new(data::SATURATED_BY_TEMPERATURE_TABLE.to_string()).unwrap();
+
+        let state = steam_table.get_state_at_point(100.0).unwrap();
+        assert_eq!(state.temperature, 100.0);
+        assert_eq!(state.pressure, 0.10142);
+        assert_eq!(state.enthalpy_vapor, 2675.6);
+        assert_eq!(state.entropy_of_vaporization, 6.0469);
+
+        let steam_table = SteamTable::new(data::SATURATED_BY_PRESSURE_TABLE.to_string()).unwrap();
+
+        let state = steam_table.get_state_at_point(1.0).unwrap();
+        assert_eq!(state.temperature, 179.878);
+        assert_eq!(state.pressure, 1.0);
+        assert_eq!(state.specific_volume_vapor, 0.19436);
+    }
+
+    #[test]
+    fn test_missing_column() {
+        let table = data::SATURATED_BY_TEMPERATURE_TABLE.replace("Enthalpy Vapor", "Enthalpy");
+
+        assert!(matches!(
+            SteamTable::new(table),
+            Err(Error::TableParsingError(_))
+        ));
+    }
+
+    #[test]
+    fn test_from_reader_without_preamble() {
+        let table = "T (°C),P (MPa),Specific Volume Liquid (m^3/kg),Specific Volume Vapor (m^3/kg),\
+Internal Energy Liquid (kJ/kg),Internal Energy Vapor (kJ/kg),Internal Energy of Vaporization (kJ/kg),\
+Enthalpy Liquid (kJ/kg),Enthalpy Vapor (kJ/kg),Enthalpy of Vaporization (kJ/kg),\
+Entropy Liquid [kJ/(kg K)],Entropy Vapor [kJ/(kg K)],Entropy of Vaporization [kJ/(kg K)]
+100,0.101418,0.00104346,1.67186,419.06,2506.5,2087.4,419.17,2675.6,2256.4,1.3072,7.3541,6.0469
+110,0.143376,0.00105158,1.20939,461.27,2518.1,2056.8,461.42,2691.1,2229.7,1.4188,7.2381,5.8193
+";
+
+        let steam_table = SteamTable::from_reader(table.as_bytes()).unwrap();
+        assert_eq!(steam_table.metadata(), &TableMetadata::default());
+
+        let state = steam_table.get_state_at_point(105.0).unwrap();
+        assert!((state.enthalpy_liquid - 440.295).abs() < 1e-3);
+    }
+
+    #[test]
+    fn test_interpolation_modes() {
+        let mut steam_table =
+            SteamTable::new(data::SATURATED_BY_PRESSURE_TABLE.to_string()).unwrap();
+        let if97 = crate::if97::If97::new();
+
+        let saturation_temperature_error = |steam_table: &SteamTable, pressure: f64| {
+            let state = steam_table.get_state_at_point(pressure).unwrap();
+            let expected = if97.saturation_temperature(pressure).unwrap();
+            (state.temperature - expected).abs()
+        };
+
+        for mode in [
+            InterpolationMode::Linear,
+            InterpolationMode::NaturalCubicSpline,
+            InterpolationMode::MonotoneCubic,
+            InterpolationMode::Lagrange(4),
+        ] {
+            steam_table.set_interpolation_mode(mode).unwrap();
+            assert_eq!(steam_table.interpolation_mode(), mode);
+
+            assert!(saturation_temperature_error(&steam_table, 0.0022) < 2e-3);
+            assert!(saturation_temperature_error(&steam_table, 0.026) < 2e-3);
+
+            let state = steam_table.get_state_at_point(1.0).unwrap();
+            assert_eq!(state.temperature, 179.878);
+        }
+
+        for mode in [InterpolationMode::Bicubic, InterpolationMode::Lagrange(1)] {
+            assert!(matches!(
+                steam_table.set_interpolation_mode(mode),
+                Err(Error::UnsupportedInterpolationMode(_))
+            ));
+        }
+        assert_eq!(
+            steam_table.interpolation_mode(),
+            InterpolationMode::Lagrange(4)
+        );
+    }
+
+    #[test]
+    fn test_column_transforms() {
+        let steam_table =
+            SteamTable::new(data::SATURATED_BY_TEMPERATURE_TABLE.to_string()).unwrap();
+        let if97 = crate::if97::If97::new();
+
+        // interpolating the pressure itself linearly is off by about 1e-3 at 0.5 °C
+        let state = steam_table.get_state_at_point(0.5).unwrap();
+        let expected = if97.saturation_pressure(0.5).unwrap();
+        assert!(((state.pressure - expected) / expected).abs() < 2e-4);
+
+        let pressure_table =
+            SteamTable::new(data::SATURATED_BY_PRESSURE_TABLE.to_string()).unwrap();
+        let state = pressure_table.get_state_at_point(0.0022).unwrap();
+        let (_, vapor) = if97.saturation_properties(state.temperature).unwrap();
+        let expected = vapor.specific_volume;
+        assert!(((state.specific_volume_vapor - expected) / expected).abs() < 1e-3);
+    }
+
+    #[test]
+    fn test_get_transport_properties_at_point() {
+        let steam_table =
+            SteamTable::new(data::SATURATED_BY_TEMPERATURE_TABLE.to_string()).unwrap();
+
+        let (liquid, vapor) = steam_table
+            .get_transport_properties_at_point(100.0)
+            .unwrap();
+        assert!((liquid.dynamic_viscosity - 281.8e-6).abs() < 1e-6);
+        assert!((liquid.thermal_conductivity - 0.6791).abs() < 2e-3);
+        assert!((liquid.prandtl_number - 1.75).abs() < 0.02);
+        assert!((vapor.dynamic_viscosity - 12.27e-6).abs() < 1e-7);
+        assert!((vapor.thermal_conductivity - 0.0248).abs() < 5e-4);
+    }
+
+    #[test]
+    fn test_surface_tension() {
+        let steam_table =
+            SteamTable::new(data::SATURATED_BY_TEMPERATURE_TABLE.to_string()).unwrap();
+        let state = steam_table.get_state_at_point(100.0).unwrap();
+        assert!((state.surface_tension.unwrap() - 58.91e-3).abs() < 1e-5);
+        let state = steam_table
+            .get_state_at_point(steam_table.largest_valid_point())
+            .unwrap();
+        assert_eq!(state.surface_tension, Some(0.0));
+
+        let steam_table = SteamTable::new(data::SATURATED_BY_PRESSURE_TABLE.to_string()).unwrap();
+        let state = steam_table.get_state_at_point(1.0).unwrap();
+        assert_eq!(state.surface_tension, surface_tension(179.878).ok());
+
+        // there is no interface in a row past the critical point
+        let table = data::SATURATED_BY_TEMPERATURE_TABLE.trim_end();
+        let last_row = table.lines().last().unwrap();
+        let past_critical = last_row.replacen("373.946", "374.5", 1);
+        let steam_table = SteamTable::new(format!("{}\n{}", table, past_critical)).unwrap();
+        let state = steam_table.get_state_at_point(374.5).unwrap();
+        assert_eq!(state.surface_tension, None);
+    }
+
+    #[test]
+    fn test_heavy_water_tables() {
+        let temperature_table = SteamTable::bundled_by_temperature(Fluid::HeavyWater).unwrap();
+        let pressure_table = SteamTable::bundled_by_pressure(Fluid::HeavyWater).unwrap();
+        assert_eq!(temperature_table.fluid(), Fluid::HeavyWater);
+        assert_eq!(pressure_table.fluid(), Fluid::HeavyWater);
+
+        // critical point at 643.847 K and 21.6618 MPa
+        assert!((temperature_table.largest_valid_point() + KELVIN_OFFSET - 643.847).abs() < 1e-9);
+        assert_eq!(pressure_table.largest_valid_point(), 21.6618);
+        let critical_state = pressure_table.get_state_at_point(21.6618).unwrap();
+        assert!((critical_state.temperature + KELVIN_OFFSET - 643.847).abs() < 1e-9);
+        assert_eq!(
+            critical_state.specific_volume_liquid,
+            critical_state.specific_volume_vapor
+        );
+        assert!((1.0 / critical_state.specific_volume_liquid - 356.0).abs() < 0.5);
+
+        // triple point at 3.819 °C and 661.59 Pa
+        assert_eq!(
+            temperature_table.smallest_valid_point(),
+            HEAVY_WATER_TRIPLE_POINT_TEMPERATURE
+        );
+        let triple_point = temperature_table
+            .get_state_at_point(HEAVY_WATER_TRIPLE_POINT_TEMPERATURE)
+            .unwrap();
+        assert!((triple_point.pressure - 661.59e-6).abs() / 661.59e-6 < 2e-3);
+
+        // normal boiling point at 101.42 °C, and 2.7395 kPa at 25 °C
+        let boiling_point = pressure_table.get_state_at_point(0.101325).unwrap();
+        assert!((boiling_point.temperature - 101.42).abs() < 0.1);
+        let state = temperature_table.get_state_at_point(25.0).unwrap();
+        assert!((state.pressure - 2.7395e-3).abs() / 2.7395e-3 < 3e-3);
+
+        // the surface tension is that of heavy water
+        assert!((state.surface_tension.unwrap() - 71.87e-3).abs() < 1e-4);
+        assert!(matches!(
+            temperature_table.get_transport_properties_at_point(25.0),
+            Err(Error::UnsupportedFluid(Fluid::HeavyWater))
+        ));
+    }
+
+    #[test]
+    fn test_free_energies() {
+        let steam_table =
+            SteamTable::new(data::SATURATED_BY_TEMPERATURE_TABLE.to_string()).unwrap();
+
+        // saturated liquid and vapor in equilibrium have the same Gibbs energy
+        let state = steam_table.get_state_at_point(100.0).unwrap();
+        assert!((state.gibbs_energy_liquid - state.gibbs_energy_vapor).abs() < 0.1);
+        assert!(
+            (state.helmholtz_energy_vapor
+                - (state.internal_energy_vapor - 373.15 * state.entropy_vapor))
+                .abs()
+                < 1e-9
+        );
+
+        let mixture = steam_table.get_mixture_at_quality(100.0, 0.5).unwrap();
+        assert!((mixture.gibbs_energy - state.gibbs_energy_liquid).abs() < 0.1);
+    }
+
+    #[test]
+    fn test_get_estimated_values_at_point() {
+        let steam_table =
+            SteamTable::new(data::SATURATED_BY_TEMPERATURE_TABLE.to_string()).unwrap();
+
+        let estimate = steam_table.get_estimated_values_at_point(2.5).unwrap();
+        assert_eq!(estimate.bounds, (2.0, 3.0));
+        assert_eq!(estimate.cell_size, 1.0);
+        assert_eq!(
+            estimate.data_point,
+            steam_table.get_values_at_point(2.5).unwrap()
+        );
+        let errors = estimate.errors.unwrap();
+        assert!(errors.iter().any(|error| *error > 0.0));
+
+        // a row is interpolated exactly
+        let estimate = steam_table.get_estimated_values_at_point(3.0).unwrap();
+        assert!(estimate.errors.unwrap().iter().all(|error| *error == 0.0));
+    }
+
+    #[test]
+    fn test_broken_table() {
+        let table = data::SATURATED_BY_TEMPERATURE_TABLE.replacen("0.00100021", "0.0010x", 1);
+        match SteamTable::new(table) {
+            Err(Error::TableParsingError(error)) => {
+                assert_eq!(error.line, Some(8));
+                assert_eq!(error.column, Some(3));
+                assert_eq!(error.text, "0.0010x");
+            }
+            _ => panic!("broken table should not parse"),
+        }
+
+        let truncated_table: String = data::SATURATED_BY_TEMPERATURE_TABLE
+            .lines()
+            .take(4)
+            .collect::<Vec<&str>>()
+            .join("\n");
+        assert!(matches!(
+            SteamTable::new(truncated_table),
+            Err(Error::TableParsingError(_))
+        ));
+    }
+
+    #[test]
+    fn test_get_values_at_property_errors() {
+        let steam_table =
+            SteamTable::new(data::SATURATED_BY_TEMPERATURE_TABLE.to_string()).unwrap();
+
+        // enthalpy of saturated vapor peaks around 235 °C so 2675.6 kJ/kg occurs twice
+        assert!(matches!(
+            steam_table.get_values_at_property(SaturatedProperty::EnthalpyVapor, 2675.6),
+            Err(Error::NonMonotonicColumn(_))
+        ));
+
+        assert!(matches!(
+            steam_table.get_values_at_property(SaturatedProperty::EntropyVapor, 20.0),
+            Err(Error::ValueOutOfRange(_, _))
+        ));
+        assert!(matches!(
+            steam_table.get_values_at_property(SaturatedProperty::Pressure, f64::NAN),
+            Err(Error::ValueOutOfRange(_, _))
+        ));
+        assert!(matches!(
+            steam_table.get_values_at_property(SaturatedProperty::Pressure, f64::INFINITY),
+            Err(Error::ValueOutOfRange(_, _))
+        ));
+        assert!(matches!(
+            steam_table.get_values_at_point(f64::NAN),
+            Err(Error::ValueOutOfRange(_, _))
+        ));
+
+        // a table of a single row finds its own values
+        let table = "T (°C),P (MPa),Specific Volume Liquid (m^3/kg),Specific Volume Vapor (m^3/kg),\
+Internal Energy Liquid (kJ/kg),Internal Energy Vapor (kJ/kg),Internal Energy of Vaporization (kJ/kg),\
+Enthalpy Liquid (kJ/kg),Enthalpy Vapor (kJ/kg),Enthalpy of Vaporization (kJ/kg),\
+Entropy Liquid [kJ/(kg K)],Entropy Vapor [kJ/(kg K)],Entropy of Vaporization [kJ/(kg K)]
+100,0.101418,0.00104346,1.67186,419.06,2506.5,2087.4,419.17,2675.6,2256.4,1.3072,7.3541,6.0469
+";
+        let steam_table = SteamTable::new(table.to_string()).unwrap();
+        let state = steam_table
+            .get_state_at_property(SaturatedProperty::Pressure, 0.101418)
+            .unwrap();
+        assert_eq!(state.temperature, 100.0);
+        assert!(matches!(
+            steam_table.get_values_at_property(SaturatedProperty::Pressure, 0.2),
+            Err(Error::ValueOutOfRange(_, _))
+        ));
+    }
+}
@@ -1,34 +1,87 @@
 use crate::error::Error;
 use crate::math::*;
+use crate::InterpolationMode;
 
 #[derive(Debug, Clone, PartialEq, PartialOrd)]
 pub struct DataPoint {
-    pub point: f32,
-    pub values: Vec<f32>,
+    pub point: f64,
+    pub values: Vec<f64>,
 }
 
-pub fn parse_to_datapoint_struct(lines: Vec<String>) -> Result<Vec<DataPoint>, Error> {
-    let datapoints = lines
-        .iter()
-        .map(|line| {
-            let mut data_points = line
-                .split(',')
-                .map(|value| value.parse::<f32>().expect("could not parse float"));
+impl DataPoint {
+    /// value of the table column at `column`, where column 0 is the point itself
+    pub fn column_value(&self, column: usize) -> f64 {
+        if column == 0 {
+            self.point
+        } else {
+            self.values[column - 1]
+        }
+    }
+}
 
-            let point = data_points.next().expect("no value taken");
-            let values: Vec<f32> = data_points.collect();
+/// Interpolated row with the rows around it and an estimate of the interpolation error
+#[derive(Debug, Clone, PartialEq)]
+pub struct EstimatedDataPoint {
+    pub data_point: DataPoint,
+    /// points of the rows around the query as (lower, upper)
+    pub bounds: (f64, f64),
+    pub cell_size: f64,
+    /// estimated error of every value, the difference between linear and quadratic
+    /// interpolation, `None` when the table has fewer than three rows
+    pub errors: Option<Vec<f64>>,
+}
 
-            DataPoint { point, values }
-        })
-        .collect();
+/// Index of the first row above `point`, kept within the rows so the cell always has two rows
+pub fn upper_row_index(datapoints: &[DataPoint], point: f64) -> usize {
+    datapoints
+        .partition_point(|datapoint| datapoint.point <= point)
+        .clamp(1, datapoints.len() - 1)
+}
 
-    Ok(datapoints)
+/// Parses the data lines of a saturated table, paired with their line numbers,
+/// every line must hold `number_of_columns` floats
+pub fn parse_to_datapoint_struct(
+    lines: Vec<crate::NumberedLine<'_>>,
+    number_of_columns: usize,
+) -> Result<Vec<DataPoint>, Error> {
+    lines
+        .iter()
+        .map(|(line_number, line)| {
+            let fields: Vec<&str> = line.split(',').collect();
+            if fields.len() != number_of_columns {
+                let message = format!(
+                    "expected {} columns, found {}",
+                    number_of_columns,
+                    fields.len()
+                );
+                return Err(Error::table_parsing(
+                    Some(*line_number),
+                    None,
+                    line,
+                    &message,
+                ));
+            }
+
+            let mut data_points = fields
+                .iter()
+                .enumerate()
+                .map(|(index, field)| crate::parse_table_float(field, *line_number, index + 1));
+
+            let point = data_points.next().unwrap()?;
+            let values = data_points.collect::<Result<Vec<f64>, Error>>()?;
+
+            Ok(DataPoint { point, values })
+        })
+        .collect()
 }
 
+/// Interpolates linearly between two rows, the values at `index` are interpolated
+/// with `transforms[index]` when it is given
 pub fn interpolate_data_points(
-    point: f32,
+    point: f64,
     min_data_point: DataPoint,
     max_data_point: DataPoint,
+    transforms: &[ColumnTransform],
 ) -> DataPoint {
     if min_data_point == max_data_point {
         return min_data_point;
@@ -43,12 +96,21 @@ pub fn interpolate_data_points(
     let values = lower_bound_values
         .iter()
         .zip(upper_bound_values)
-        .map(|pair| {
-            let point0 = Point2(lower_bound, *pair.0);
-
-            let point1 = Point2(upper_bound, pair.1);
-
-            linear_interpolate(point, point0, point1)
+        .enumerate()
+        .map(|(index, pair)| match transforms.get(index) {
+            Some(transform) => transform.interpolate(
+                InterpolationMode::Linear,
+                point,
+                &[lower_bound, upper_bound],
+                &[*pair.0, pair.1],
+            ),
+            None => {
+                let point0 = Point2(lower_bound, *pair.0);
+
+                let point1 = Point2(upper_bound, pair.1);
+
+                linear_interpolate(point, point0, point1)
+            }
         })
         .collect();
 
@@ -78,10 +140,57 @@ mod tests {
             values: vec![0.5, 0.5, 0.5, 0.5, 0.5],
         };
 
-        let lerp1 = interpolate_data_points(0.5, data_point_0.clone(), data_point_1);
+        let lerp1 = interpolate_data_points(0.5, data_point_0.clone(), data_point_1.clone(), &[]);
         assert_eq!(lerp1, data_point_mid);
 
-        let lerp2 = interpolate_data_points(0.0, data_point_0.clone(), data_point_0.clone());
+        let lerp2 = interpolate_data_points(0.0, data_point_0.clone(), data_point_0.clone(), &[]);
         assert_eq!(lerp2, data_point_0);
+
+        let reciprocal = ColumnTransform::new(Transform::Identity, Transform::Reciprocal);
+        let data_point_2 = DataPoint {
+            point: 1.0,
+            values: vec![4.0; 5],
+        };
+        let data_point_0 = DataPoint {
+            point: 0.0,
+            values: vec![1.0; 5],
+        };
+        let lerp3 = interpolate_data_points(0.5, data_point_0, data_point_2, &[reciprocal]);
+        assert_eq!(lerp3.values[0], 1.6);
+        assert_eq!(lerp3.values[1], 2.5);
+    }
+
+    #[test]
+    fn test_parse_to_datapoint_struct() {
+        let lines = vec![(8, "0.01, 0.000611657, 0.001"), (9, "5, 0.00087, 0.001")];
+        let datapoints = parse_to_datapoint_struct(lines, 3).unwrap();
+        assert_eq!(datapoints[1].point, 5.0);
+        assert_eq!(datapoints[1].values, vec![0.00087, 0.001]);
+    }
+
+    #[test]
+    fn test_parse_to_datapoint_struct_errors() {
+        let error = parse_to_datapoint_struct(vec![(8, "0.01, 0.0006"), (9, "5, abc, 0.001")], 3)
+            .unwrap_err();
+        match error {
+            Error::TableParsingError(error) => {
+                assert_eq!(error.line, Some(8));
+                assert_eq!(error.column, None);
+            }
+            _ => panic!("unexpected error {:?}", error),
+        }
+
+        let error = parse_to_datapoint_struct(vec![(9, "5, abc, 0.001")], 3).unwrap_err();
+        match error {
+            Error::TableParsingError(error) => {
+                assert_eq!(error.line, Some(9));
+                assert_eq!(error.column, Some(2));
+                assert_eq!(error.text, "abc");
+            }
+            _ => panic!("unexpected error {:?}", error),
+        }
+
+        let error = parse_to_datapoint_struct(vec![(9, "5, NaN, 0.001")], 3).unwrap_err();
+        assert!(matches!(error, Error::TableParsingError(_)));
     }
 }
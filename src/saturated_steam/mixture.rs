@@ -0,0 +1,104 @@
+use super::SaturatedProperty;
+use crate::error::Error;
+
+/// Properties of a two-phase mixture of saturated liquid and saturated vapor
+#[derive(Debug, Clone, PartialEq)]
+pub struct MixtureState {
+    pub temperature: f64,
+    pub pressure: f64,
+    /// mass fraction of vapor in the mixture
+    pub quality: f64,
+    pub specific_volume: f64,
+    pub internal_energy: f64,
+    pub enthalpy: f64,
+    pub entropy: f64,
+    /// h - T s
+    pub gibbs_energy: f64,
+    /// u - T s
+    pub helmholtz_energy: f64,
+}
+
+impl MixtureState {
+    /// value of `property` of the mixture
+    pub fn value(&self, property: MixtureProperty) -> f64 {
+        match property {
+            MixtureProperty::SpecificVolume => self.specific_volume,
+            MixtureProperty::InternalEnergy => self.internal_energy,
+            MixtureProperty::Enthalpy => self.enthalpy,
+            MixtureProperty::Entropy => self.entropy,
+        }
+    }
+}
+
+/// Properties of a mixture which can be used to find its quality
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MixtureProperty {
+    SpecificVolume,
+    InternalEnergy,
+    Enthalpy,
+    Entropy,
+}
+
+impl MixtureProperty {
+    /// columns of the saturated liquid and saturated vapor values of the property
+    pub fn saturated_properties(self) -> (SaturatedProperty, SaturatedProperty) {
+        match self {
+            MixtureProperty::SpecificVolume => (
+                SaturatedProperty::SpecificVolumeLiquid,
+                SaturatedProperty::SpecificVolumeVapor,
+            ),
+            MixtureProperty::InternalEnergy => (
+                SaturatedProperty::InternalEnergyLiquid,
+                SaturatedProperty::InternalEnergyVapor,
+            ),
+            MixtureProperty::Enthalpy => (
+                SaturatedProperty::EnthalpyLiquid,
+                SaturatedProperty::EnthalpyVapor,
+            ),
+            MixtureProperty::Entropy => (
+                SaturatedProperty::EntropyLiquid,
+                SaturatedProperty::EntropyVapor,
+            ),
+        }
+    }
+}
+
+/// value of a property of the mixture, weighted by quality between the liquid and vapor values
+pub fn mix_values(liquid_value: f64, vapor_value: f64, quality: f64) -> f64 {
+    liquid_value + quality * (vapor_value - liquid_value)
+}
+
+/// quality of a mixture whose property equals `value`
+///
+/// returns `Error::ValueOutOfRange` if `value` does not lie between the liquid and vapor values,
+/// which a non-finite value never does
+pub fn quality_from_value(value: f64, liquid_value: f64, vapor_value: f64) -> Result<f64, Error> {
+    let smallest_value = liquid_value.min(vapor_value);
+    let largest_value = liquid_value.max(vapor_value);
+
+    if !(smallest_value..=largest_value).contains(&value) {
+        return Err(Error::ValueOutOfRange(smallest_value, largest_value));
+    }
+
+    // liquid and vapor are indistinguishable at the critical point
+    if liquid_value == vapor_value {
+        return Ok(0.0);
+    }
+
+    Ok((value - liquid_value) / (vapor_value - liquid_value))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_quality_from_value() {
+        assert_eq!(quality_from_value(1.5, 1.0, 2.0).unwrap(), 0.5);
+        assert_eq!(quality_from_value(1.0, 1.0, 1.0).unwrap(), 0.0);
+        assert!(quality_from_value(2.5, 1.0, 2.0).is_err());
+        assert!(quality_from_value(f64::NAN, 1.0, 2.0).is_err());
+        assert!(quality_from_value(f64::INFINITY, 1.0, 2.0).is_err());
+        assert_eq!(mix_values(1.0, 2.0, 0.25), 1.25);
+    }
+}
@@ -0,0 +1,150 @@
+use crate::error::Error;
+use crate::math::{ColumnTransform, Transform};
+
+/// Columns of the saturated steam tables which can be used to look up a saturation state
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SaturatedProperty {
+    Temperature,
+    Pressure,
+    SpecificVolumeLiquid,
+    SpecificVolumeVapor,
+    InternalEnergyLiquid,
+    InternalEnergyVapor,
+    InternalEnergyOfVaporization,
+    EnthalpyLiquid,
+    EnthalpyVapor,
+    EnthalpyOfVaporization,
+    EntropyLiquid,
+    EntropyVapor,
+    EntropyOfVaporization,
+}
+
+impl SaturatedProperty {
+    pub const ALL: [SaturatedProperty; 13] = [
+        SaturatedProperty::Temperature,
+        SaturatedProperty::Pressure,
+        SaturatedProperty::SpecificVolumeLiquid,
+        SaturatedProperty::SpecificVolumeVapor,
+        SaturatedProperty::InternalEnergyLiquid,
+        SaturatedProperty::InternalEnergyVapor,
+        SaturatedProperty::InternalEnergyOfVaporization,
+        SaturatedProperty::EnthalpyLiquid,
+        SaturatedProperty::EnthalpyVapor,
+        SaturatedProperty::EnthalpyOfVaporization,
+        SaturatedProperty::EntropyLiquid,
+        SaturatedProperty::EntropyVapor,
+        SaturatedProperty::EntropyOfVaporization,
+    ];
+
+    /// start of the header of the column holding the property
+    pub fn header_label(self) -> &'static str {
+        match self {
+            SaturatedProperty::Temperature => "T (",
+            SaturatedProperty::Pressure => "P (",
+            SaturatedProperty::SpecificVolumeLiquid => "Specific Volume Liquid",
+            SaturatedProperty::SpecificVolumeVapor => "Specific Volume Vapor",
+            SaturatedProperty::InternalEnergyLiquid => "Internal Energy Liquid",
+            SaturatedProperty::InternalEnergyVapor => "Internal Energy Vapor",
+            SaturatedProperty::InternalEnergyOfVaporization => "Internal Energy of Vaporization",
+            SaturatedProperty::EnthalpyLiquid => "Enthalpy Liquid",
+            SaturatedProperty::EnthalpyVapor => "Enthalpy Vapor",
+            SaturatedProperty::EnthalpyOfVaporization => "Enthalpy of Vaporization",
+            SaturatedProperty::EntropyLiquid => "Entropy Liquid",
+            SaturatedProperty::EntropyVapor => "Entropy Vapor",
+            SaturatedProperty::EntropyOfVaporization => "Entropy of Vaporization",
+        }
+    }
+}
+
+impl SaturatedProperty {
+    /// Transform used to interpolate the property between the rows of a table
+    /// indexed by temperature or by pressure
+    ///
+    /// saturation pressure and vapor specific volume vary exponentially, their logarithm
+    /// is interpolated against the reciprocal temperature or the logarithm of the pressure
+    pub fn column_transform(self, temperature_indexed: bool) -> ColumnTransform {
+        let point_transform = if temperature_indexed {
+            Transform::ReciprocalAbsoluteTemperature
+        } else {
+            Transform::Logarithm
+        };
+
+        match self {
+            SaturatedProperty::Pressure => {
+                ColumnTransform::new(point_transform, Transform::Logarithm)
+            }
+            SaturatedProperty::Temperature => {
+                ColumnTransform::new(point_transform, Transform::ReciprocalAbsoluteTemperature)
+            }
+            SaturatedProperty::SpecificVolumeVapor => {
+                ColumnTransform::new(point_transform, Transform::Logarithm)
+            }
+            _ => ColumnTransform::IDENTITY,
+        }
+    }
+}
+
+/// Finds the transform of every value column of a table from the columns of its properties
+///
+/// the returned vector is indexed like `DataPoint::values`
+pub fn find_column_transforms(columns: &[usize], number_of_values: usize) -> Vec<ColumnTransform> {
+    let temperature_indexed = columns[SaturatedProperty::Temperature as usize] == 0;
+    let mut transforms = vec![ColumnTransform::IDENTITY; number_of_values];
+
+    for property in SaturatedProperty::ALL {
+        let column = columns[property as usize];
+        if column > 0 {
+            transforms[column - 1] = property.column_transform(temperature_indexed);
+        }
+    }
+
+    transforms
+}
+
+/// Finds the column of every saturated property from the table headers
+///
+/// the returned vector is indexed by `SaturatedProperty as usize`
+pub fn find_property_columns(headers: &[String], header_line: usize) -> Result<Vec<usize>, Error> {
+    SaturatedProperty::ALL
+        .iter()
+        .map(|property| {
+            headers
+                .iter()
+                .position(|header| header.starts_with(property.header_label()))
+                .ok_or_else(|| {
+                    Error::table_parsing(
+                        Some(header_line),
+                        None,
+                        property.header_label(),
+                        "missing column",
+                    )
+                })
+        })
+        .collect()
+}
+
+/// Properties of saturated liquid and saturated vapor at a saturation point
+#[derive(Debug, Clone, PartialEq)]
+pub struct SaturatedState {
+    pub temperature: f64,
+    pub pressure: f64,
+    pub specific_volume_liquid: f64,
+    pub specific_volume_vapor: f64,
+    pub internal_energy_liquid: f64,
+    pub internal_energy_vapor: f64,
+    pub internal_energy_of_vaporization: f64,
+    pub enthalpy_liquid: f64,
+    pub enthalpy_vapor: f64,
+    pub enthalpy_of_vaporization: f64,
+    pub entropy_liquid: f64,
+    pub entropy_vapor: f64,
+    pub entropy_of_vaporization: f64,
+    /// h - T s, equal for saturated liquid and vapor in equilibrium
+    pub gibbs_energy_liquid: f64,
+    pub gibbs_energy_vapor: f64,
+    /// u - T s
+    pub helmholtz_energy_liquid: f64,
+    pub helmholtz_energy_vapor: f64,
+    /// N/m, zero at the critical point and none above it, where there is no interface
+    pub surface_tension: Option<f64>,
+}
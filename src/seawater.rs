@@ -0,0 +1,242 @@
+//! Seawater and brine from the states of pure water plus the saline contributions of
+//! the correlations of Sharqawy, Lienhard and Zubair (2010)
+//!
+//! salinity is the absolute salinity in g/kg, the saline contributions are fitted at
+//! atmospheric pressure and added to the pure-water state at the pressure of the query
+
+use crate::backend::{SaturationProperties, SinglePhaseProperties};
+use crate::error::Error;
+use crate::regions;
+
+/// highest salinity in g/kg of the correlations
+pub const MAXIMUM_SALINITY: f64 = 120.0;
+
+/// temperatures in °C the enthalpy and entropy correlations are fitted over
+const TEMPERATURE_RANGE: (f64, f64) = (10.0, 120.0);
+/// temperatures in °C the boiling point elevation is fitted over
+const BOILING_TEMPERATURE_RANGE: (f64, f64) = (0.0, 200.0);
+
+/// b_i of the saline density in kg/m^3, S b1 + S t b2 + S t² b3 + S t³ b4 + S² t² b5
+const DENSITY_COEFFICIENTS: [f64; 5] = [8.020e2, -2.001, 1.677e-2, -3.060e-5, -1.613e-5];
+
+/// a_i of the saline enthalpy in J/kg
+const ENTHALPY_COEFFICIENTS: [f64; 10] = [
+    -2.348e4, 3.152e5, 2.803e6, -1.446e7, 7.826e3, -4.417e1, 2.139e-1, -1.991e4, 2.778e4, 9.728e1,
+];
+
+/// b_i of the saline entropy in J/(kg K)
+const ENTROPY_COEFFICIENTS: [f64; 10] = [
+    -4.231e2, 1.463e4, -9.880e4, 3.095e5, 2.562e1, -1.443e-1, 5.879e-4, -6.111e1, 8.041e1, 3.035e-1,
+];
+
+/// Properties of seawater at a pressure, temperature and salinity, in the units of the tables
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SeawaterState {
+    pub pressure: f64,
+    pub temperature: f64,
+    /// g/kg
+    pub salinity: f64,
+    pub specific_volume: f64,
+    pub density: f64,
+    pub enthalpy: f64,
+    pub entropy: f64,
+}
+
+/// Seawater built on the pure-water states of `water`, such as a `WaterTable`
+#[derive(Debug, Clone)]
+pub struct Seawater<B> {
+    water: B,
+}
+
+impl<B> Seawater<B> {
+    pub fn new(water: B) -> Seawater<B> {
+        Seawater { water }
+    }
+
+    /// the source of the pure-water states
+    pub fn water(&self) -> &B {
+        &self.water
+    }
+}
+
+impl<B: SinglePhaseProperties> Seawater<B> {
+    /// Seawater of `salinity` in g/kg at `pressure` in MPa and `temperature` in °C
+    ///
+    /// pure water must be liquid at the point, so `pressure` must not be below its
+    /// saturation pressure
+    pub fn state(
+        &self,
+        pressure: f64,
+        temperature: f64,
+        salinity: f64,
+    ) -> Result<SeawaterState, Error> {
+        is_salinity_valid(salinity)?;
+        is_temperature_valid(temperature, TEMPERATURE_RANGE)?;
+
+        let water = self.water.single_phase_state(pressure, temperature)?;
+        if !water.phase.is_liquid() {
+            return Err(Error::ValueOutOfRange(
+                regions::saturation_pressure(temperature)?,
+                f64::INFINITY,
+            ));
+        }
+
+        let salinity_fraction = salinity / 1000.0;
+        let density = water.density + saline_density(temperature, salinity_fraction);
+
+        Ok(SeawaterState {
+            pressure,
+            temperature,
+            salinity,
+            specific_volume: 1.0 / density,
+            density,
+            // J/kg to kJ/kg
+            enthalpy: water.enthalpy
+                - salinity_fraction
+                    * saline_term(&ENTHALPY_COEFFICIENTS, temperature, salinity_fraction)
+                    / 1000.0,
+            entropy: water.entropy
+                - salinity_fraction
+                    * saline_term(&ENTROPY_COEFFICIENTS, temperature, salinity_fraction)
+                    / 1000.0,
+        })
+    }
+}
+
+impl<B: SaturationProperties> Seawater<B> {
+    /// boiling temperature in °C of seawater of `salinity` in g/kg at `pressure` in MPa,
+    /// the saturation temperature of pure water raised by the boiling point elevation
+    pub fn boiling_temperature(&self, pressure: f64, salinity: f64) -> Result<f64, Error> {
+        let saturation_temperature = self
+            .water
+            .saturation_state_at_pressure(pressure)?
+            .temperature;
+
+        Ok(saturation_temperature + boiling_point_elevation(saturation_temperature, salinity)?)
+    }
+}
+
+/// boiling point elevation in K of seawater of `salinity` in g/kg boiling at `temperature` in °C
+pub fn boiling_point_elevation(temperature: f64, salinity: f64) -> Result<f64, Error> {
+    is_salinity_valid(salinity)?;
+    is_temperature_valid(temperature, BOILING_TEMPERATURE_RANGE)?;
+
+    let salinity = salinity / 1000.0;
+    let a = -4.584e-4 * temperature.powi(2) + 2.823e-1 * temperature + 17.95;
+    let b = 1.536e-4 * temperature.powi(2) + 5.267e-2 * temperature + 6.56;
+
+    Ok(a * salinity.powi(2) + b * salinity)
+}
+
+fn is_salinity_valid(salinity: f64) -> Result<(), Error> {
+    if !(0.0..=MAXIMUM_SALINITY).contains(&salinity) {
+        return Err(Error::ValueOutOfRange(0.0, MAXIMUM_SALINITY));
+    }
+
+    Ok(())
+}
+
+fn is_temperature_valid(temperature: f64, (minimum, maximum): (f64, f64)) -> Result<(), Error> {
+    if !(minimum..=maximum).contains(&temperature) {
+        return Err(Error::ValueOutOfRange(minimum, maximum));
+    }
+
+    Ok(())
+}
+
+/// density in kg/m^3 added to pure water at `temperature` in °C by `salinity` in kg/kg
+fn saline_density(temperature: f64, salinity: f64) -> f64 {
+    let b = &DENSITY_COEFFICIENTS;
+
+    salinity
+        * (b[0]
+            + b[1] * temperature
+            + b[2] * temperature.powi(2)
+            + b[3] * temperature.powi(3)
+            + b[4] * salinity * temperature.powi(2))
+}
+
+/// the polynomial in `salinity` in kg/kg and `temperature` in °C shared by the enthalpy
+/// and entropy correlations, which subtract `salinity` times it from pure water
+fn saline_term(coefficients: &[f64; 10], temperature: f64, salinity: f64) -> f64 {
+    let c = coefficients;
+    let (s, t) = (salinity, temperature);
+
+    c[0] + c[1] * s
+        + c[2] * s.powi(2)
+        + c[3] * s.powi(3)
+        + c[4] * t
+        + c[5] * t.powi(2)
+        + c[6] * t.powi(3)
+        + c[7] * s * t
+        + c[8] * s.powi(2) * t
+        + c[9] * s * t.powi(2)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data;
+    use crate::water::WaterTable;
+
+    fn seawater() -> Seawater<WaterTable> {
+        Seawater::new(
+            WaterTable::new(data::COMPRESSED_LIQUID_SUPERHEATED_STEAM.to_string()).unwrap(),
+        )
+    }
+
+    #[test]
+    fn test_seawater_state() {
+        let seawater = seawater();
+
+        // standard seawater at 25 °C and atmospheric pressure
+        let state = seawater.state(0.1, 25.0, 35.0).unwrap();
+        assert!((state.density - 1023.3).abs() < 1.0);
+        assert!((state.enthalpy - 99.8).abs() < 0.5);
+        assert!((state.entropy - 0.350).abs() < 2e-3);
+
+        // without salt the state is that of pure water
+        let water = seawater.water().get_state_at_point(0.1, 25.0).unwrap();
+        let state = seawater.state(0.1, 25.0, 0.0).unwrap();
+        assert_eq!(state.density, water.density);
+        assert_eq!(state.enthalpy, water.enthalpy);
+        assert_eq!(state.entropy, water.entropy);
+
+        // brine is denser than seawater
+        assert!(seawater.state(0.1, 25.0, 100.0).unwrap().density > 1060.0);
+    }
+
+    #[test]
+    fn test_ranges() {
+        let seawater = seawater();
+
+        assert!(matches!(
+            seawater.state(0.1, 25.0, 150.0),
+            Err(Error::ValueOutOfRange(0.0, MAXIMUM_SALINITY))
+        ));
+        assert!(matches!(
+            seawater.state(0.1, 25.0, -1.0),
+            Err(Error::ValueOutOfRange(_, _))
+        ));
+        assert!(matches!(
+            seawater.state(0.1, 150.0, 35.0),
+            Err(Error::ValueOutOfRange(10.0, 120.0))
+        ));
+        // pure water boils at 0.05 MPa and 100 °C
+        assert!(matches!(
+            seawater.state(0.05, 100.0, 35.0),
+            Err(Error::ValueOutOfRange(_, _))
+        ));
+    }
+
+    #[test]
+    fn test_boiling_point_elevation() {
+        assert_eq!(boiling_point_elevation(100.0, 0.0).unwrap(), 0.0);
+        let elevation = boiling_point_elevation(100.0, 35.0).unwrap();
+        assert!((elevation - 0.52).abs() < 0.02);
+        assert!(boiling_point_elevation(100.0, 70.0).unwrap() > 2.0 * elevation);
+
+        let boiling_temperature = seawater().boiling_temperature(0.101325, 35.0).unwrap();
+        assert!((boiling_temperature - 100.52).abs() < 0.05);
+    }
+}
@@ -0,0 +1,559 @@
+//! Viscosity after IAPWS 2008, thermal conductivity after IAPWS 2011 and the surface tension
+//! of saturated water after IAPWS 2014 and of saturated heavy water after IAPWS 1994
+//!
+//! both are evaluated from the density and temperature of a state, the critical enhancement
+//! of the thermal conductivity also uses its heat capacities and compressibility as in the
+//! industrial formulation, while that of the viscosity is taken as 1 as recommended for industrial use
+
+use crate::constants::{
+    self, CRITICAL_DENSITY, CRITICAL_PRESSURE, CRITICAL_TEMPERATURE_KELVIN, KELVIN_OFFSET,
+};
+use crate::error::Error;
+use crate::water::DerivedProperties;
+
+/// specific gas constant in kJ/(kg K) used by the thermal conductivity formulation
+const GAS_CONSTANT: f64 = 0.46151805;
+
+/// H_i of the viscosity in the dilute-gas limit
+const VISCOSITY_DILUTE_GAS: [f64; 4] = [1.67752, 2.20462, 0.6366564, -0.241605];
+
+/// (i, j, H_ij) of the residual viscosity, i is the power of 1 / T̄ - 1 and j that of ρ̄ - 1
+const VISCOSITY_RESIDUAL: [(i32, i32, f64); 21] = [
+    (0, 0, 5.20094e-1),
+    (1, 0, 8.50895e-2),
+    (2, 0, -1.08374),
+    (3, 0, -2.89555e-1),
+    (0, 1, 2.22531e-1),
+    (1, 1, 9.99115e-1),
+    (2, 1, 1.88797),
+    (3, 1, 1.26613),
+    (5, 1, 1.20573e-1),
+    (0, 2, -2.81378e-1),
+    (1, 2, -9.06851e-1),
+    (2, 2, -7.72479e-1),
+    (3, 2, -4.89837e-1),
+    (4, 2, -2.57040e-1),
+    (0, 3, 1.61913e-1),
+    (1, 3, 2.57399e-1),
+    (0, 4, -3.25372e-2),
+    (3, 4, 6.98452e-2),
+    (4, 5, 8.72102e-3),
+    (3, 6, -4.35673e-3),
+    (5, 6, -5.93264e-4),
+];
+
+/// L_k of the thermal conductivity in the dilute-gas limit
+const CONDUCTIVITY_DILUTE_GAS: [f64; 5] = [
+    2.443221e-3,
+    1.323095e-2,
+    6.770357e-3,
+    -3.454586e-3,
+    4.096266e-4,
+];
+
+/// L_ij of the residual thermal conductivity, i is the power of 1 / T̄ - 1 and j that of ρ̄ - 1
+const CONDUCTIVITY_RESIDUAL: [[f64; 6]; 5] = [
+    [
+        1.60397357,
+        -0.646013523,
+        0.111443906,
+        0.102997357,
+        -0.0504123634,
+        0.00609859258,
+    ],
+    [
+        2.33771842,
+        -2.78843778,
+        1.53616167,
+        -0.463045512,
+        0.0832827019,
+        -0.00719201245,
+    ],
+    [
+        2.19650529,
+        -4.54580785,
+        3.55777244,
+        -1.40944978,
+        0.275418278,
+        -0.0205938816,
+    ],
+    [
+        -1.21051378,
+        1.60812989,
+        -0.621178141,
+        0.0716373224,
+        0.0,
+        0.0,
+    ],
+    [
+        -2.7203370,
+        4.57586331,
+        -3.18369245,
+        1.1168348,
+        -0.19268305,
+        0.012913842,
+    ],
+];
+
+/// upper reduced densities of the ranges of `REFERENCE_COMPRESSIBILITY`
+const REFERENCE_DENSITY_RANGES: [f64; 4] = [0.310559006, 0.776397516, 1.242236025, 1.863354037];
+
+/// A_ij of the reduced compressibility at the reference temperature, one row per density range
+const REFERENCE_COMPRESSIBILITY: [[f64; 6]; 5] = [
+    [
+        6.53786807199516,
+        -5.61149954923348,
+        3.39624167361325,
+        -2.27492629730878,
+        10.2631854662709,
+        1.97815050331519,
+    ],
+    [
+        6.52717759281799,
+        -6.30816983387575,
+        8.08379285492595,
+        -9.82240510197603,
+        12.1358413791395,
+        -5.54349664571295,
+    ],
+    [
+        5.35500529896124,
+        -3.96415689925446,
+        8.91990208918795,
+        -12.0338729505790,
+        9.19494865194302,
+        -2.16866274479712,
+    ],
+    [
+        1.55225959906681,
+        0.464621290821181,
+        8.93237374861479,
+        -11.0321960061126,
+        6.16780999933360,
+        -0.965458722086812,
+    ],
+    [
+        1.11999926419994,
+        0.595748562571649,
+        9.88952565078920,
+        -10.3255051147040,
+        4.66861294457414,
+        -0.503243546373828,
+    ],
+];
+
+/// reduced reference temperature of the critical enhancement
+const REFERENCE_TEMPERATURE: f64 = 1.5;
+/// Λ
+const ENHANCEMENT_AMPLITUDE: f64 = 177.8514;
+/// ξ0 in nm
+const CORRELATION_LENGTH: f64 = 0.13;
+/// Γ0
+const SUSCEPTIBILITY_AMPLITUDE: f64 = 0.06;
+/// ν / γ
+const CRITICAL_EXPONENT: f64 = 0.630 / 1.239;
+/// 1 / q̄D in nm
+const CUTOFF_LENGTH: f64 = 0.40;
+
+/// B, b and μ of the surface tension B τ^μ (1 + b τ) in N/m
+const SURFACE_TENSION: (f64, f64, f64) = (235.8e-3, -0.625, 1.256);
+/// B, b and μ of the surface tension of heavy water
+const HEAVY_WATER_SURFACE_TENSION: (f64, f64, f64) = (238.0e-3, -0.639, 1.25);
+
+/// Transport properties of water at a state
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TransportProperties {
+    /// Pa s
+    pub dynamic_viscosity: f64,
+    /// m^2/s
+    pub kinematic_viscosity: f64,
+    /// W/(m K)
+    pub thermal_conductivity: f64,
+    pub prandtl_number: f64,
+}
+
+impl TransportProperties {
+    /// Transport properties at `density` in kg/m^3 and `temperature` in °C,
+    /// `derived_properties` gives the heat capacities and compressibility of the state
+    pub fn new(
+        density: f64,
+        temperature: f64,
+        derived_properties: &DerivedProperties,
+    ) -> TransportProperties {
+        let dynamic_viscosity = viscosity(density, temperature);
+        let thermal_conductivity = thermal_conductivity(density, temperature, derived_properties);
+
+        TransportProperties {
+            dynamic_viscosity,
+            kinematic_viscosity: dynamic_viscosity / density,
+            thermal_conductivity,
+            prandtl_number: dynamic_viscosity * derived_properties.isobaric_heat_capacity * 1000.0
+                / thermal_conductivity,
+        }
+    }
+}
+
+/// dynamic viscosity in Pa s at `density` in kg/m^3 and `temperature` in °C
+pub fn viscosity(density: f64, temperature: f64) -> f64 {
+    let (density, temperature) = reduce(density, temperature);
+
+    // in μPa s
+    viscosity_dilute_gas(temperature) * viscosity_residual(density, temperature) * 1.0e-6
+}
+
+/// thermal conductivity in W/(m K) at `density` in kg/m^3 and `temperature` in °C
+pub fn thermal_conductivity(
+    density: f64,
+    temperature: f64,
+    derived_properties: &DerivedProperties,
+) -> f64 {
+    let viscosity = viscosity(density, temperature) * 1.0e6;
+    let (density, temperature) = reduce(density, temperature);
+
+    // in mW/(m K)
+    let conductivity = conductivity_dilute_gas(temperature)
+        * conductivity_residual(density, temperature)
+        + conductivity_critical_enhancement(density, temperature, viscosity, derived_properties);
+
+    conductivity * 1.0e-3
+}
+
+/// reduced density and temperature
+fn reduce(density: f64, temperature: f64) -> (f64, f64) {
+    (
+        density / CRITICAL_DENSITY,
+        (temperature + KELVIN_OFFSET) / CRITICAL_TEMPERATURE_KELVIN,
+    )
+}
+
+fn viscosity_dilute_gas(temperature: f64) -> f64 {
+    let sum: f64 = VISCOSITY_DILUTE_GAS
+        .iter()
+        .enumerate()
+        .map(|(i, h)| h / temperature.powi(i as i32))
+        .sum();
+
+    100.0 * temperature.sqrt() / sum
+}
+
+fn viscosity_residual(density: f64, temperature: f64) -> f64 {
+    let sum: f64 = VISCOSITY_RESIDUAL
+        .iter()
+        .map(|&(i, j, h)| h * (1.0 / temperature - 1.0).powi(i) * (density - 1.0).powi(j))
+        .sum();
+
+    (density * sum).exp()
+}
+
+fn conductivity_dilute_gas(temperature: f64) -> f64 {
+    let sum: f64 = CONDUCTIVITY_DILUTE_GAS
+        .iter()
+        .enumerate()
+        .map(|(k, l)| l / temperature.powi(k as i32))
+        .sum();
+
+    temperature.sqrt() / sum
+}
+
+fn conductivity_residual(density: f64, temperature: f64) -> f64 {
+    let sum: f64 = CONDUCTIVITY_RESIDUAL
+        .iter()
+        .enumerate()
+        .map(|(i, row)| {
+            let row_sum: f64 = row
+                .iter()
+                .enumerate()
+                .map(|(j, l)| l * (density - 1.0).powi(j as i32))
+                .sum();
+
+            (1.0 / temperature - 1.0).powi(i as i32) * row_sum
+        })
+        .sum();
+
+    (density * sum).exp()
+}
+
+/// critical enhancement λ̄2 from the reduced density, temperature and viscosity in μPa s
+fn conductivity_critical_enhancement(
+    density: f64,
+    temperature: f64,
+    viscosity: f64,
+    derived_properties: &DerivedProperties,
+) -> f64 {
+    let isobaric_heat_capacity = derived_properties.isobaric_heat_capacity;
+    let heat_capacity_ratio = isobaric_heat_capacity / derived_properties.isochoric_heat_capacity;
+
+    // ζ = (pc / ρc) (∂ρ/∂p)_T with (∂ρ/∂p)_T = ρ κT
+    let compressibility = CRITICAL_PRESSURE / CRITICAL_DENSITY
+        * density
+        * CRITICAL_DENSITY
+        * derived_properties.isothermal_compressibility;
+    let susceptibility = density
+        * (compressibility
+            - reference_compressibility(density) * REFERENCE_TEMPERATURE / temperature);
+    if susceptibility.is_nan() || susceptibility <= 0.0 || !heat_capacity_ratio.is_finite() {
+        return 0.0;
+    }
+
+    let correlation_length =
+        CORRELATION_LENGTH * (susceptibility / SUSCEPTIBILITY_AMPLITUDE).powf(CRITICAL_EXPONENT);
+    let y = correlation_length / CUTOFF_LENGTH;
+    if y < 1.2e-7 {
+        return 0.0;
+    }
+
+    let z = 2.0 / (std::f64::consts::PI * y)
+        * ((1.0 - 1.0 / heat_capacity_ratio) * y.atan() + y / heat_capacity_ratio
+            - (1.0 - (-1.0 / (1.0 / y + y * y / (3.0 * density * density))).exp()));
+
+    ENHANCEMENT_AMPLITUDE * density * isobaric_heat_capacity / GAS_CONSTANT * temperature
+        / viscosity
+        * z
+}
+
+/// surface tension in N/m of water against its vapor at the saturation `temperature` in °C
+///
+/// vanishes at the critical point, above which there is no interface, the correlation holds
+/// down to the triple point and `Error::ValueOutOfRange` is returned outside of that range
+pub fn surface_tension(temperature: f64) -> Result<f64, Error> {
+    surface_tension_between(
+        temperature,
+        (
+            constants::TRIPLE_POINT_TEMPERATURE,
+            constants::CRITICAL_TEMPERATURE,
+        ),
+        SURFACE_TENSION,
+    )
+}
+
+/// surface tension in N/m of heavy water against its vapor at the saturation `temperature` in °C
+///
+/// holds like that of water between the triple and critical points, here those of heavy water
+pub fn heavy_water_surface_tension(temperature: f64) -> Result<f64, Error> {
+    surface_tension_between(
+        temperature,
+        (
+            constants::HEAVY_WATER_TRIPLE_POINT_TEMPERATURE,
+            constants::HEAVY_WATER_CRITICAL_TEMPERATURE,
+        ),
+        HEAVY_WATER_SURFACE_TENSION,
+    )
+}
+
+/// B τ^μ (1 + b τ) between the triple and critical temperatures in °C
+fn surface_tension_between(
+    temperature: f64,
+    (triple_temperature, critical_temperature): (f64, f64),
+    (amplitude, b, exponent): (f64, f64, f64),
+) -> Result<f64, Error> {
+    // compared in °C so that the critical point of the tables is not pushed above it by rounding
+    if !(triple_temperature..=critical_temperature).contains(&temperature) {
+        return Err(Error::ValueOutOfRange(
+            triple_temperature,
+            critical_temperature,
+        ));
+    }
+
+    let tau =
+        (1.0 - (temperature + KELVIN_OFFSET) / (critical_temperature + KELVIN_OFFSET)).max(0.0);
+    Ok(amplitude * tau.powf(exponent) * (1.0 + b * tau))
+}
+
+/// reduced compressibility ζ at the reference temperature and the reduced `density`
+fn reference_compressibility(density: f64) -> f64 {
+    let range = REFERENCE_DENSITY_RANGES.partition_point(|upper| *upper < density);
+
+    let sum: f64 = REFERENCE_COMPRESSIBILITY[range]
+        .iter()
+        .enumerate()
+        .map(|(i, a)| a * density.powi(i as i32))
+        .sum();
+
+    1.0 / sum
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn celsius(temperature: f64) -> f64 {
+        temperature - KELVIN_OFFSET
+    }
+
+    fn assert_relative_eq(value: f64, expected: f64, tolerance: f64) {
+        let relative_error = ((value - expected) / expected).abs();
+        assert!(
+            relative_error < tolerance,
+            "{} differs from {} by {}",
+            value,
+            expected,
+            relative_error
+        );
+    }
+
+    #[test]
+    fn test_viscosity_verification_values() {
+        // (T in K, ρ in kg/m^3, μ in μPa s) of the release
+        let values = [
+            (298.15, 998.0, 889.735100),
+            (298.15, 1200.0, 1437.649467),
+            (373.15, 1000.0, 307.883622),
+            (433.15, 1.0, 14.538324),
+            (433.15, 1000.0, 217.685358),
+            (873.15, 1.0, 32.619287),
+            (873.15, 100.0, 35.802262),
+            (873.15, 600.0, 77.430195),
+            (1173.15, 1.0, 44.217245),
+            (1173.15, 100.0, 47.640433),
+            (1173.15, 400.0, 64.154608),
+        ];
+
+        for (temperature, density, expected) in values {
+            let value = viscosity(density, celsius(temperature)) * 1.0e6;
+            assert_relative_eq(value, expected, 1e-6);
+        }
+    }
+
+    #[test]
+    fn test_thermal_conductivity_without_enhancement() {
+        // (T in K, ρ in kg/m^3, λ0 λ1 in mW/(m K))
+        let values = [
+            (298.15, 0.0, 18.4341883),
+            (298.15, 998.0, 607.712868),
+            (298.15, 1200.0, 799.038144),
+            (873.15, 0.0, 79.1034659),
+        ];
+
+        for (temperature, density, expected) in values {
+            let (density, temperature) = reduce(density, celsius(temperature));
+            let value =
+                conductivity_dilute_gas(temperature) * conductivity_residual(density, temperature);
+            assert_relative_eq(value, expected, 1e-6);
+        }
+    }
+
+    #[test]
+    fn test_transport_properties() {
+        use crate::backend::SinglePhaseProperties;
+        use crate::data;
+        use crate::water::WaterTable;
+
+        let water_table =
+            WaterTable::new(data::COMPRESSED_LIQUID_SUPERHEATED_STEAM.to_string()).unwrap();
+        let properties = water_table.transport_properties(0.1, 25.0).unwrap();
+        assert_relative_eq(properties.dynamic_viscosity, 890.0e-6, 2e-3);
+        assert_relative_eq(properties.kinematic_viscosity, 0.8927e-6, 2e-3);
+        assert_relative_eq(properties.thermal_conductivity, 0.6072, 2e-3);
+        assert_relative_eq(properties.prandtl_number, 6.13, 1e-2);
+    }
+
+    #[test]
+    fn test_thermal_conductivity_critical_enhancement() {
+        use crate::backend::SinglePhaseProperties;
+        use crate::if97::region3;
+
+        // (ρ in kg/m^3, λ in mW/(m K)) of the release at T = 647.35 K, with the tolerance of each,
+        // the derivatives are those of IAPWS-IF97 rather than IAPWS-95, which differ the most
+        // next to the critical density where the enhancement dominates
+        let values = [
+            (122.0, 130.922885, 1e-3),
+            (222.0, 367.787459, 5e-3),
+            (272.0, 757.959776, 0.1),
+            (322.0, 1443.75556, 0.15),
+            (372.0, 650.319402, 5e-2),
+            (422.0, 448.883487, 1e-3),
+            (750.0, 600.961346, 1e-4),
+        ];
+
+        for (density, expected, tolerance) in values {
+            let derived_properties = region3::properties(density, 647.35).derived_properties();
+            let value = thermal_conductivity(density, celsius(647.35), &derived_properties) * 1.0e3;
+            assert_relative_eq(value, expected, tolerance);
+        }
+
+        // the conductivity rises sharply towards the critical point
+        let if97 = crate::if97::If97::new();
+        let near_critical = if97.transport_properties(22.5, 375.0).unwrap();
+        let derived_properties = if97.derived_properties(22.5, 375.0).unwrap();
+        let state = if97.single_phase_state(22.5, 375.0).unwrap();
+        let (density, temperature) = reduce(state.density, 375.0);
+        let background = conductivity_dilute_gas(temperature)
+            * conductivity_residual(density, temperature)
+            * 1.0e-3;
+        assert!(derived_properties.isobaric_heat_capacity > 20.0);
+        assert!(near_critical.thermal_conductivity > 1.3 * background);
+    }
+
+    #[test]
+    fn test_surface_tension() {
+        // (T in K, σ in mN/m) of the release
+        let values = [
+            (273.16, 75.65),
+            (300.0, 71.69),
+            (373.15, 58.91),
+            (600.0, 8.39),
+        ];
+
+        for (temperature, expected) in values {
+            let value = surface_tension(celsius(temperature)).unwrap() * 1.0e3;
+            assert_relative_eq(value, expected, 2e-3);
+        }
+
+        assert_eq!(
+            surface_tension(constants::CRITICAL_TEMPERATURE).unwrap(),
+            0.0
+        );
+        assert!(matches!(
+            surface_tension(400.0),
+            Err(Error::ValueOutOfRange(_, _))
+        ));
+        // supercooled water lies outside of the validity of the correlation
+        assert!(matches!(
+            surface_tension(-5.0),
+            Err(Error::ValueOutOfRange(_, _))
+        ));
+        assert!(surface_tension(f64::NAN).is_err());
+    }
+
+    #[test]
+    fn test_heavy_water_surface_tension() {
+        // (T in K, σ in mN/m)
+        let values = [(298.15, 71.87), (373.15, 58.93)];
+
+        for (temperature, expected) in values {
+            let value = heavy_water_surface_tension(celsius(temperature)).unwrap() * 1.0e3;
+            assert_relative_eq(value, expected, 2e-3);
+        }
+
+        assert_eq!(
+            heavy_water_surface_tension(constants::HEAVY_WATER_CRITICAL_TEMPERATURE).unwrap(),
+            0.0
+        );
+        // between the triple points of water and heavy water
+        assert!(surface_tension(1.0).is_ok());
+        assert!(matches!(
+            heavy_water_surface_tension(1.0),
+            Err(Error::ValueOutOfRange(_, _))
+        ));
+    }
+
+    #[test]
+    fn test_reference_compressibility() {
+        // ζ approaches that of an ideal gas, pc / (ρc R TR Tc), at low density
+        let ideal_gas = CRITICAL_PRESSURE * 1000.0
+            / (CRITICAL_DENSITY
+                * GAS_CONSTANT
+                * REFERENCE_TEMPERATURE
+                * CRITICAL_TEMPERATURE_KELVIN);
+        assert_relative_eq(reference_compressibility(1e-6), ideal_gas, 1e-3);
+
+        // the fits of the ranges about join
+        for upper in REFERENCE_DENSITY_RANGES {
+            assert_relative_eq(
+                reference_compressibility(upper - 1e-9),
+                reference_compressibility(upper + 1e-9),
+                5e-3,
+            );
+        }
+    }
+}
@@ -0,0 +1,829 @@
+use std::borrow::Borrow;
+
+use crate::backend::{SaturationProperties, SinglePhaseProperties};
+use crate::constants::KELVIN_OFFSET;
+use crate::error::Error;
+use crate::saturated_steam::{
+    MixtureProperty, MixtureState, SaturatedProperty, SaturatedState, SteamTable,
+};
+use crate::transport::TransportProperties;
+use crate::water::{DerivedProperties, FlashState, SinglePhaseState, WaterTable};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PressureUnit {
+    MegaPascal,
+    KiloPascal,
+    Pascal,
+    Bar,
+    Psi,
+    Atmosphere,
+}
+
+impl PressureUnit {
+    /// number of units in one MPa
+    fn per_megapascal(self) -> f64 {
+        match self {
+            PressureUnit::MegaPascal => 1.0,
+            PressureUnit::KiloPascal => 1.0e3,
+            PressureUnit::Pascal => 1.0e6,
+            PressureUnit::Bar => 10.0,
+            PressureUnit::Psi => 145.037_74,
+            PressureUnit::Atmosphere => 9.869_233,
+        }
+    }
+
+    /// converts `value` in this unit to MPa
+    pub fn to_table_units(self, value: f64) -> f64 {
+        value / self.per_megapascal()
+    }
+
+    /// converts `value` in MPa to this unit
+    pub fn from_table_units(self, value: f64) -> f64 {
+        value * self.per_megapascal()
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TemperatureUnit {
+    Celsius,
+    Kelvin,
+    Fahrenheit,
+    Rankine,
+}
+
+impl TemperatureUnit {
+    /// converts `value` in this unit to °C
+    pub fn to_table_units(self, value: f64) -> f64 {
+        match self {
+            TemperatureUnit::Celsius => value,
+            TemperatureUnit::Kelvin => value - KELVIN_OFFSET,
+            TemperatureUnit::Fahrenheit => (value - 32.0) * 5.0 / 9.0,
+            TemperatureUnit::Rankine => value * 5.0 / 9.0 - KELVIN_OFFSET,
+        }
+    }
+
+    /// converts `value` in °C to this unit
+    pub fn from_table_units(self, value: f64) -> f64 {
+        match self {
+            TemperatureUnit::Celsius => value,
+            TemperatureUnit::Kelvin => value + KELVIN_OFFSET,
+            TemperatureUnit::Fahrenheit => value * 9.0 / 5.0 + 32.0,
+            TemperatureUnit::Rankine => (value + KELVIN_OFFSET) * 9.0 / 5.0,
+        }
+    }
+
+    /// converts a temperature difference `value` in K to this unit
+    pub fn difference_from_table_units(self, value: f64) -> f64 {
+        self.from_table_units(value) - self.from_table_units(0.0)
+    }
+}
+
+/// Units of specific internal energy and specific enthalpy
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SpecificEnergyUnit {
+    KilojoulePerKilogram,
+    JoulePerKilogram,
+    BtuPerPound,
+}
+
+impl SpecificEnergyUnit {
+    /// number of units in one kJ/kg
+    fn per_kilojoule_per_kilogram(self) -> f64 {
+        match self {
+            SpecificEnergyUnit::KilojoulePerKilogram => 1.0,
+            SpecificEnergyUnit::JoulePerKilogram => 1.0e3,
+            SpecificEnergyUnit::BtuPerPound => 0.429_922_6,
+        }
+    }
+
+    /// converts `value` in this unit to kJ/kg
+    pub fn to_table_units(self, value: f64) -> f64 {
+        value / self.per_kilojoule_per_kilogram()
+    }
+
+    /// converts `value` in kJ/kg to this unit
+    pub fn from_table_units(self, value: f64) -> f64 {
+        value * self.per_kilojoule_per_kilogram()
+    }
+}
+
+/// Units of specific entropy and specific heat capacity
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SpecificEntropyUnit {
+    KilojoulePerKilogramKelvin,
+    JoulePerKilogramKelvin,
+    BtuPerPoundRankine,
+}
+
+impl SpecificEntropyUnit {
+    /// number of units in one kJ/(kg K)
+    fn per_kilojoule_per_kilogram_kelvin(self) -> f64 {
+        match self {
+            SpecificEntropyUnit::KilojoulePerKilogramKelvin => 1.0,
+            SpecificEntropyUnit::JoulePerKilogramKelvin => 1.0e3,
+            SpecificEntropyUnit::BtuPerPoundRankine => 0.238_845_9,
+        }
+    }
+
+    /// converts `value` in this unit to kJ/(kg K)
+    pub fn to_table_units(self, value: f64) -> f64 {
+        value / self.per_kilojoule_per_kilogram_kelvin()
+    }
+
+    /// converts `value` in kJ/(kg K) to this unit
+    pub fn from_table_units(self, value: f64) -> f64 {
+        value * self.per_kilojoule_per_kilogram_kelvin()
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SpecificVolumeUnit {
+    CubicMeterPerKilogram,
+    CubicFootPerPound,
+}
+
+impl SpecificVolumeUnit {
+    /// number of units in one m^3/kg
+    fn per_cubic_meter_per_kilogram(self) -> f64 {
+        match self {
+            SpecificVolumeUnit::CubicMeterPerKilogram => 1.0,
+            SpecificVolumeUnit::CubicFootPerPound => 16.018_463,
+        }
+    }
+
+    /// converts `value` in this unit to m^3/kg
+    pub fn to_table_units(self, value: f64) -> f64 {
+        value / self.per_cubic_meter_per_kilogram()
+    }
+
+    /// converts `value` in m^3/kg to this unit
+    pub fn from_table_units(self, value: f64) -> f64 {
+        value * self.per_cubic_meter_per_kilogram()
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DensityUnit {
+    KilogramPerCubicMeter,
+    PoundPerCubicFoot,
+}
+
+impl DensityUnit {
+    /// number of units in one kg/m^3
+    fn per_kilogram_per_cubic_meter(self) -> f64 {
+        match self {
+            DensityUnit::KilogramPerCubicMeter => 1.0,
+            DensityUnit::PoundPerCubicFoot => 0.062_427_96,
+        }
+    }
+
+    /// converts `value` in this unit to kg/m^3
+    pub fn to_table_units(self, value: f64) -> f64 {
+        value / self.per_kilogram_per_cubic_meter()
+    }
+
+    /// converts `value` in kg/m^3 to this unit
+    pub fn from_table_units(self, value: f64) -> f64 {
+        value * self.per_kilogram_per_cubic_meter()
+    }
+}
+
+/// Units used for the inputs and outputs of queries
+///
+/// the tables use MPa, °C, kJ/kg, kJ/(kg K), m^3/kg and kg/m^3
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct UnitSystem {
+    pub pressure: PressureUnit,
+    pub temperature: TemperatureUnit,
+    pub specific_energy: SpecificEnergyUnit,
+    pub specific_entropy: SpecificEntropyUnit,
+    pub specific_volume: SpecificVolumeUnit,
+    pub density: DensityUnit,
+}
+
+impl UnitSystem {
+    /// units of the bundled tables
+    pub fn table() -> UnitSystem {
+        UnitSystem {
+            pressure: PressureUnit::MegaPascal,
+            temperature: TemperatureUnit::Celsius,
+            specific_energy: SpecificEnergyUnit::KilojoulePerKilogram,
+            specific_entropy: SpecificEntropyUnit::KilojoulePerKilogramKelvin,
+            specific_volume: SpecificVolumeUnit::CubicMeterPerKilogram,
+            density: DensityUnit::KilogramPerCubicMeter,
+        }
+    }
+
+    /// psi, °F, BTU/lb, BTU/(lb R), ft^3/lb and lb/ft^3
+    pub fn english() -> UnitSystem {
+        UnitSystem {
+            pressure: PressureUnit::Psi,
+            temperature: TemperatureUnit::Fahrenheit,
+            specific_energy: SpecificEnergyUnit::BtuPerPound,
+            specific_entropy: SpecificEntropyUnit::BtuPerPoundRankine,
+            specific_volume: SpecificVolumeUnit::CubicFootPerPound,
+            density: DensityUnit::PoundPerCubicFoot,
+        }
+    }
+}
+
+impl Default for UnitSystem {
+    fn default() -> Self {
+        UnitSystem::table()
+    }
+}
+
+/// Kind of an input of a query, choosing its unit in a `UnitSystem`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Quantity {
+    Pressure,
+    Temperature,
+    SpecificEnergy,
+    SpecificEntropy,
+    SpecificVolume,
+    /// quality, the same in every unit system
+    Fraction,
+}
+
+impl From<MixtureProperty> for Quantity {
+    fn from(property: MixtureProperty) -> Self {
+        match property {
+            MixtureProperty::SpecificVolume => Quantity::SpecificVolume,
+            MixtureProperty::InternalEnergy | MixtureProperty::Enthalpy => Quantity::SpecificEnergy,
+            MixtureProperty::Entropy => Quantity::SpecificEntropy,
+        }
+    }
+}
+
+impl From<SaturatedProperty> for Quantity {
+    fn from(property: SaturatedProperty) -> Self {
+        match property {
+            SaturatedProperty::Temperature => Quantity::Temperature,
+            SaturatedProperty::Pressure => Quantity::Pressure,
+            SaturatedProperty::SpecificVolumeLiquid | SaturatedProperty::SpecificVolumeVapor => {
+                Quantity::SpecificVolume
+            }
+            SaturatedProperty::InternalEnergyLiquid
+            | SaturatedProperty::InternalEnergyVapor
+            | SaturatedProperty::InternalEnergyOfVaporization
+            | SaturatedProperty::EnthalpyLiquid
+            | SaturatedProperty::EnthalpyVapor
+            | SaturatedProperty::EnthalpyOfVaporization => Quantity::SpecificEnergy,
+            SaturatedProperty::EntropyLiquid
+            | SaturatedProperty::EntropyVapor
+            | SaturatedProperty::EntropyOfVaporization => Quantity::SpecificEntropy,
+        }
+    }
+}
+
+impl UnitSystem {
+    fn quantity_to_table_units(&self, quantity: Quantity, value: f64) -> f64 {
+        match quantity {
+            Quantity::Pressure => self.pressure.to_table_units(value),
+            Quantity::Temperature => self.temperature.to_table_units(value),
+            Quantity::SpecificEnergy => self.specific_energy.to_table_units(value),
+            Quantity::SpecificEntropy => self.specific_entropy.to_table_units(value),
+            Quantity::SpecificVolume => self.specific_volume.to_table_units(value),
+            Quantity::Fraction => value,
+        }
+    }
+
+    fn quantity_from_table_units(&self, quantity: Quantity, value: f64) -> f64 {
+        match quantity {
+            Quantity::Pressure => self.pressure.from_table_units(value),
+            Quantity::Temperature => self.temperature.from_table_units(value),
+            Quantity::SpecificEnergy => self.specific_energy.from_table_units(value),
+            Quantity::SpecificEntropy => self.specific_entropy.from_table_units(value),
+            Quantity::SpecificVolume => self.specific_volume.from_table_units(value),
+            Quantity::Fraction => value,
+        }
+    }
+}
+
+/// States whose properties can be expressed in another unit system
+pub trait ConvertUnits {
+    /// converts a state in table units to `units`
+    fn to_units(&self, units: &UnitSystem) -> Self;
+}
+
+impl ConvertUnits for SaturatedState {
+    fn to_units(&self, units: &UnitSystem) -> Self {
+        let energy = |value| units.specific_energy.from_table_units(value);
+        let entropy = |value| units.specific_entropy.from_table_units(value);
+        let volume = |value| units.specific_volume.from_table_units(value);
+
+        SaturatedState {
+            temperature: units.temperature.from_table_units(self.temperature),
+            pressure: units.pressure.from_table_units(self.pressure),
+            specific_volume_liquid: volume(self.specific_volume_liquid),
+            specific_volume_vapor: volume(self.specific_volume_vapor),
+            internal_energy_liquid: energy(self.internal_energy_liquid),
+            internal_energy_vapor: energy(self.internal_energy_vapor),
+            internal_energy_of_vaporization: energy(self.internal_energy_of_vaporization),
+            enthalpy_liquid: energy(self.enthalpy_liquid),
+            enthalpy_vapor: energy(self.enthalpy_vapor),
+            enthalpy_of_vaporization: energy(self.enthalpy_of_vaporization),
+            entropy_liquid: entropy(self.entropy_liquid),
+            entropy_vapor: entropy(self.entropy_vapor),
+            entropy_of_vaporization: entropy(self.entropy_of_vaporization),
+            gibbs_energy_liquid: energy(self.gibbs_energy_liquid),
+            gibbs_energy_vapor: energy(self.gibbs_energy_vapor),
+            helmholtz_energy_liquid: energy(self.helmholtz_energy_liquid),
+            helmholtz_energy_vapor: energy(self.helmholtz_energy_vapor),
+            // in SI like the transport properties
+            surface_tension: self.surface_tension,
+        }
+    }
+}
+
+impl ConvertUnits for SinglePhaseState {
+    fn to_units(&self, units: &UnitSystem) -> Self {
+        SinglePhaseState {
+            pressure: units.pressure.from_table_units(self.pressure),
+            temperature: units.temperature.from_table_units(self.temperature),
+            specific_volume: units.specific_volume.from_table_units(self.specific_volume),
+            density: units.density.from_table_units(self.density),
+            internal_energy: units.specific_energy.from_table_units(self.internal_energy),
+            enthalpy: units.specific_energy.from_table_units(self.enthalpy),
+            entropy: units.specific_entropy.from_table_units(self.entropy),
+            gibbs_energy: units.specific_energy.from_table_units(self.gibbs_energy),
+            helmholtz_energy: units
+                .specific_energy
+                .from_table_units(self.helmholtz_energy),
+            phase: self.phase,
+            derived_properties: self
+                .derived_properties
+                .map(|properties| properties.to_units(units)),
+        }
+    }
+}
+
+impl ConvertUnits for MixtureState {
+    fn to_units(&self, units: &UnitSystem) -> Self {
+        MixtureState {
+            temperature: units.temperature.from_table_units(self.temperature),
+            pressure: units.pressure.from_table_units(self.pressure),
+            quality: self.quality,
+            specific_volume: units.specific_volume.from_table_units(self.specific_volume),
+            internal_energy: units.specific_energy.from_table_units(self.internal_energy),
+            enthalpy: units.specific_energy.from_table_units(self.enthalpy),
+            entropy: units.specific_entropy.from_table_units(self.entropy),
+            gibbs_energy: units.specific_energy.from_table_units(self.gibbs_energy),
+            helmholtz_energy: units
+                .specific_energy
+                .from_table_units(self.helmholtz_energy),
+        }
+    }
+}
+
+/// the speed of sound stays in m/s
+impl ConvertUnits for DerivedProperties {
+    fn to_units(&self, units: &UnitSystem) -> Self {
+        let per_pressure = units.pressure.from_table_units(1.0);
+        let per_temperature = units.temperature.difference_from_table_units(1.0);
+
+        DerivedProperties {
+            isobaric_heat_capacity: units
+                .specific_entropy
+                .from_table_units(self.isobaric_heat_capacity),
+            isochoric_heat_capacity: units
+                .specific_entropy
+                .from_table_units(self.isochoric_heat_capacity),
+            speed_of_sound: self.speed_of_sound,
+            isothermal_compressibility: self.isothermal_compressibility / per_pressure,
+            volumetric_expansion_coefficient: self.volumetric_expansion_coefficient
+                / per_temperature,
+            joule_thomson_coefficient: self.joule_thomson_coefficient * per_temperature
+                / per_pressure,
+        }
+    }
+}
+
+impl ConvertUnits for FlashState {
+    fn to_units(&self, units: &UnitSystem) -> Self {
+        match self {
+            FlashState::SinglePhase(state) => FlashState::SinglePhase(state.to_units(units)),
+            FlashState::TwoPhase(mixture) => FlashState::TwoPhase(mixture.to_units(units)),
+        }
+    }
+}
+
+/// Property backend queried and answering in the units of `units`
+///
+/// the bounds of `Error::ValueOutOfRange` are converted to `units` with the unit of the
+/// first input lying outside them, wrapped water and saturated steam tables also take
+/// their flashes and mixture queries in `units`
+pub struct WithUnits<B> {
+    pub backend: B,
+    pub units: UnitSystem,
+}
+
+impl<B> WithUnits<B> {
+    pub fn new(backend: B, units: UnitSystem) -> WithUnits<B> {
+        WithUnits { backend, units }
+    }
+
+    /// converts the bounds of an `Error::ValueOutOfRange` from table units to `units`,
+    /// taking the quantity of the first of `inputs`, in table units, outside the bounds
+    fn error_to_units(&self, error: Error, inputs: &[(f64, Quantity)]) -> Error {
+        let Error::ValueOutOfRange(minimum, maximum) = error else {
+            return error;
+        };
+
+        match inputs
+            .iter()
+            .find(|(value, _)| !(minimum..=maximum).contains(value))
+        {
+            Some(&(_, quantity)) => Error::ValueOutOfRange(
+                self.units.quantity_from_table_units(quantity, minimum),
+                self.units.quantity_from_table_units(quantity, maximum),
+            ),
+            None => error,
+        }
+    }
+}
+
+impl<B: SaturationProperties> SaturationProperties for WithUnits<B> {
+    fn saturation_state_at_temperature(&self, temperature: f64) -> Result<SaturatedState, Error> {
+        let temperature = self.units.temperature.to_table_units(temperature);
+
+        let state = self
+            .backend
+            .saturation_state_at_temperature(temperature)
+            .map_err(|error| self.error_to_units(error, &[(temperature, Quantity::Temperature)]))?;
+        Ok(state.to_units(&self.units))
+    }
+
+    fn saturation_state_at_pressure(&self, pressure: f64) -> Result<SaturatedState, Error> {
+        let pressure = self.units.pressure.to_table_units(pressure);
+
+        let state = self
+            .backend
+            .saturation_state_at_pressure(pressure)
+            .map_err(|error| self.error_to_units(error, &[(pressure, Quantity::Pressure)]))?;
+        Ok(state.to_units(&self.units))
+    }
+}
+
+impl<B: SinglePhaseProperties> SinglePhaseProperties for WithUnits<B> {
+    fn single_phase_state(
+        &self,
+        pressure: f64,
+        temperature: f64,
+    ) -> Result<SinglePhaseState, Error> {
+        let pressure = self.units.pressure.to_table_units(pressure);
+        let temperature = self.units.temperature.to_table_units(temperature);
+
+        let state = self
+            .backend
+            .single_phase_state(pressure, temperature)
+            .map_err(|error| self.single_phase_error_to_units(error, pressure, temperature))?;
+        Ok(state.to_units(&self.units))
+    }
+
+    fn derived_properties(
+        &self,
+        pressure: f64,
+        temperature: f64,
+    ) -> Result<DerivedProperties, Error> {
+        let pressure = self.units.pressure.to_table_units(pressure);
+        let temperature = self.units.temperature.to_table_units(temperature);
+
+        let properties = self
+            .backend
+            .derived_properties(pressure, temperature)
+            .map_err(|error| self.single_phase_error_to_units(error, pressure, temperature))?;
+        Ok(properties.to_units(&self.units))
+    }
+
+    /// the transport properties stay in SI units
+    fn transport_properties(
+        &self,
+        pressure: f64,
+        temperature: f64,
+    ) -> Result<TransportProperties, Error> {
+        let pressure = self.units.pressure.to_table_units(pressure);
+        let temperature = self.units.temperature.to_table_units(temperature);
+
+        self.backend
+            .transport_properties(pressure, temperature)
+            .map_err(|error| self.single_phase_error_to_units(error, pressure, temperature))
+    }
+}
+
+impl<B> WithUnits<B> {
+    fn single_phase_error_to_units(&self, error: Error, pressure: f64, temperature: f64) -> Error {
+        self.error_to_units(
+            error,
+            &[
+                (pressure, Quantity::Pressure),
+                (temperature, Quantity::Temperature),
+            ],
+        )
+    }
+}
+
+impl<B: Borrow<WaterTable>> WithUnits<B> {
+    /// `WaterTable::get_values_at_pressure_enthalpy` in `units`
+    pub fn get_values_at_pressure_enthalpy(
+        &self,
+        pressure: f64,
+        enthalpy: f64,
+    ) -> Result<FlashState, Error> {
+        self.flash_at_property(pressure, MixtureProperty::Enthalpy, enthalpy)
+    }
+
+    /// `WaterTable::get_values_at_pressure_entropy` in `units`
+    pub fn get_values_at_pressure_entropy(
+        &self,
+        pressure: f64,
+        entropy: f64,
+    ) -> Result<FlashState, Error> {
+        self.flash_at_property(pressure, MixtureProperty::Entropy, entropy)
+    }
+
+    fn flash_at_property(
+        &self,
+        pressure: f64,
+        property: MixtureProperty,
+        value: f64,
+    ) -> Result<FlashState, Error> {
+        let water_table = self.backend.borrow();
+        let pressure = self.units.pressure.to_table_units(pressure);
+        let value = self.units.quantity_to_table_units(property.into(), value);
+
+        // checked first, as the bounds of `value` need not contain `pressure`
+        water_table
+            .is_pressure_valid(pressure)
+            .map_err(|error| self.error_to_units(error, &[(pressure, Quantity::Pressure)]))?;
+
+        let state = match property {
+            MixtureProperty::Enthalpy => {
+                water_table.get_values_at_pressure_enthalpy(pressure, value)
+            }
+            _ => water_table.get_values_at_pressure_entropy(pressure, value),
+        }
+        .map_err(|error| self.error_to_units(error, &[(value, property.into())]))?;
+        Ok(state.to_units(&self.units))
+    }
+}
+
+impl<B: Borrow<SteamTable>> WithUnits<B> {
+    /// quantity of the saturation points of the wrapped table
+    fn point_quantity(&self) -> Quantity {
+        if self.backend.borrow().is_temperature_indexed() {
+            Quantity::Temperature
+        } else {
+            Quantity::Pressure
+        }
+    }
+
+    /// `SteamTable::get_state_at_property` in `units`
+    pub fn get_state_at_property(
+        &self,
+        property: SaturatedProperty,
+        value: f64,
+    ) -> Result<SaturatedState, Error> {
+        let value = self.units.quantity_to_table_units(property.into(), value);
+
+        let state = self
+            .backend
+            .borrow()
+            .get_state_at_property(property, value)
+            .map_err(|error| self.error_to_units(error, &[(value, property.into())]))?;
+        Ok(state.to_units(&self.units))
+    }
+
+    /// `SteamTable::get_mixture_at_quality` in `units`, `point` in the unit of the
+    /// pressure or temperature indexing the table
+    pub fn get_mixture_at_quality(&self, point: f64, quality: f64) -> Result<MixtureState, Error> {
+        let point_quantity = self.point_quantity();
+        let point = self.units.quantity_to_table_units(point_quantity, point);
+
+        let mixture = self
+            .backend
+            .borrow()
+            .get_mixture_at_quality(point, quality)
+            .map_err(|error| {
+                self.error_to_units(
+                    error,
+                    &[(quality, Quantity::Fraction), (point, point_quantity)],
+                )
+            })?;
+        Ok(mixture.to_units(&self.units))
+    }
+
+    /// `SteamTable::get_mixture_at_property` in `units`, `point` in the unit of the
+    /// pressure or temperature indexing the table
+    pub fn get_mixture_at_property(
+        &self,
+        point: f64,
+        property: MixtureProperty,
+        value: f64,
+    ) -> Result<MixtureState, Error> {
+        let steam_table = self.backend.borrow();
+        let point_quantity = self.point_quantity();
+        let point = self.units.quantity_to_table_units(point_quantity, point);
+        let value = self.units.quantity_to_table_units(property.into(), value);
+
+        // checked first, as the two-phase bounds of `value` need not contain `point`
+        steam_table
+            .is_point_valid(point)
+            .map_err(|error| self.error_to_units(error, &[(point, point_quantity)]))?;
+
+        let mixture = steam_table
+            .get_mixture_at_property(point, property, value)
+            .map_err(|error| self.error_to_units(error, &[(value, property.into())]))?;
+        Ok(mixture.to_units(&self.units))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data;
+    use crate::water::WaterTable;
+
+    fn assert_close(value: f64, expected: f64) {
+        assert!(
+            ((value - expected) / expected).abs() < 1e-5,
+            "{} is not close to {}",
+            value,
+            expected
+        );
+    }
+
+    #[test]
+    fn test_temperature_conversions() {
+        assert_close(TemperatureUnit::Fahrenheit.from_table_units(100.0), 212.0);
+        assert_close(TemperatureUnit::Fahrenheit.to_table_units(212.0), 100.0);
+        assert_close(TemperatureUnit::Rankine.from_table_units(100.0), 671.67);
+        assert_close(TemperatureUnit::Kelvin.to_table_units(373.15), 100.0);
+    }
+
+    #[test]
+    fn test_pressure_conversions() {
+        assert_close(PressureUnit::Bar.to_table_units(10.0), 1.0);
+        assert_close(PressureUnit::KiloPascal.to_table_units(101.325), 0.101325);
+        assert_close(PressureUnit::Psi.to_table_units(14.695_95), 0.101325);
+        assert_close(PressureUnit::Atmosphere.from_table_units(0.101325), 1.0);
+    }
+
+    #[test]
+    fn test_water_table_with_units() {
+        let water_table =
+            WaterTable::new(data::COMPRESSED_LIQUID_SUPERHEATED_STEAM.to_string()).unwrap();
+        let units = UnitSystem {
+            pressure: PressureUnit::Bar,
+            temperature: TemperatureUnit::Kelvin,
+            ..UnitSystem::english()
+        };
+        let water_table_with_units = WithUnits::new(&water_table, units);
+
+        let state = water_table_with_units
+            .single_phase_state(10.0, 573.15)
+            .unwrap();
+        let table_state = water_table.get_state_at_point(1.0, 300.0).unwrap();
+
+        assert_close(state.pressure, 10.0);
+        assert_close(state.temperature, 573.15);
+        assert_close(state.enthalpy, table_state.enthalpy * 0.429_922_6);
+        assert_close(state.entropy, table_state.entropy * 0.238_845_9);
+        assert_close(
+            state.specific_volume,
+            table_state.specific_volume * 16.018_463,
+        );
+    }
+
+    #[test]
+    fn test_flashes_and_mixtures_with_units() {
+        let water_table =
+            WaterTable::new(data::COMPRESSED_LIQUID_SUPERHEATED_STEAM.to_string()).unwrap();
+        let units = UnitSystem::english();
+        let water_table_with_units = WithUnits::new(&water_table, units);
+
+        let pressure = units.pressure.from_table_units(1.0);
+        let enthalpy = units.specific_energy.from_table_units(3051.6);
+        let state = water_table_with_units
+            .get_values_at_pressure_enthalpy(pressure, enthalpy)
+            .unwrap();
+        let table_state = water_table
+            .get_values_at_pressure_enthalpy(1.0, 3051.6)
+            .unwrap();
+        assert_eq!(state, table_state.to_units(&units));
+
+        let entropy = units.specific_entropy.from_table_units(6.0);
+        let state = water_table_with_units
+            .get_values_at_pressure_entropy(pressure, entropy)
+            .unwrap();
+        let table_state = water_table
+            .get_values_at_pressure_entropy(1.0, 6.0)
+            .unwrap();
+        assert_close(state.quality().unwrap(), table_state.quality().unwrap());
+        assert_close(
+            state.enthalpy(),
+            units
+                .specific_energy
+                .from_table_units(table_state.enthalpy()),
+        );
+
+        let steam_table = water_table.saturation_table();
+        let steam_table_with_units = WithUnits::new(steam_table, units);
+        let mixture = steam_table_with_units
+            .get_mixture_at_property(pressure, MixtureProperty::Enthalpy, enthalpy * 0.8)
+            .unwrap();
+        let table_mixture = steam_table
+            .get_mixture_at_property(1.0, MixtureProperty::Enthalpy, 3051.6 * 0.8)
+            .unwrap();
+        assert_close(mixture.quality, table_mixture.quality);
+        assert_close(
+            mixture.temperature,
+            units
+                .temperature
+                .from_table_units(table_mixture.temperature),
+        );
+
+        let mixture = steam_table_with_units
+            .get_mixture_at_quality(pressure, 0.5)
+            .unwrap();
+        assert_eq!(
+            mixture,
+            steam_table
+                .get_mixture_at_quality(1.0, 0.5)
+                .unwrap()
+                .to_units(&units)
+        );
+
+        let state = steam_table_with_units
+            .get_state_at_property(SaturatedProperty::Temperature, 212.0)
+            .unwrap();
+        // found between the rows of the table by pressure
+        assert!((state.temperature - 212.0).abs() < 0.05);
+    }
+
+    #[test]
+    fn test_errors_with_units() {
+        let water_table =
+            WaterTable::new(data::COMPRESSED_LIQUID_SUPERHEATED_STEAM.to_string()).unwrap();
+        let units = UnitSystem {
+            pressure: PressureUnit::Bar,
+            ..UnitSystem::english()
+        };
+        let water_table_with_units = WithUnits::new(&water_table, units);
+
+        // the pressure range of the table in bar
+        match water_table_with_units.get_values_at_pressure_enthalpy(1.0e5, 1000.0) {
+            Err(Error::ValueOutOfRange(minimum, maximum)) => {
+                assert_close(minimum, water_table.get_minimum_allowable_pressure() * 10.0);
+                assert_close(maximum, water_table.get_maximum_allowable_pressure() * 10.0);
+            }
+            other => panic!("expected a pressure range, got {:?}", other),
+        }
+
+        // the temperature range of the saturated table in °F
+        match water_table_with_units.saturation_state_at_temperature(1000.0) {
+            Err(Error::ValueOutOfRange(minimum, maximum)) => {
+                assert_close(minimum, 32.018);
+                assert_close(maximum, 705.1028);
+            }
+            other => panic!("expected a temperature range, got {:?}", other),
+        }
+
+        // the quality range stays the same
+        let steam_table_with_units = WithUnits::new(water_table.saturation_table(), units);
+        assert!(matches!(
+            steam_table_with_units.get_mixture_at_quality(10.0, 2.0),
+            Err(Error::ValueOutOfRange(0.0, 1.0))
+        ));
+    }
+
+    #[test]
+    fn test_derived_properties_with_units() {
+        let if97 = crate::if97::If97::new();
+        let units = UnitSystem {
+            pressure: PressureUnit::Bar,
+            ..UnitSystem::english()
+        };
+        let if97_with_units = WithUnits::new(if97, units);
+
+        let properties = if97_with_units.derived_properties(10.0, 572.0).unwrap();
+        let table_properties = if97.derived_properties(1.0, 300.0).unwrap();
+
+        assert_close(
+            properties.isobaric_heat_capacity,
+            table_properties.isobaric_heat_capacity * 0.238_845_9,
+        );
+        assert_close(
+            properties.isothermal_compressibility,
+            table_properties.isothermal_compressibility / 10.0,
+        );
+        assert_close(
+            properties.volumetric_expansion_coefficient,
+            table_properties.volumetric_expansion_coefficient * 5.0 / 9.0,
+        );
+        assert_close(
+            properties.joule_thomson_coefficient,
+            table_properties.joule_thomson_coefficient * 1.8 / 10.0,
+        );
+    }
+}
@@ -1,147 +1,665 @@
+use std::{fs::File, io::Read, path::Path};
+
+use crate::backend::SinglePhaseProperties;
 use crate::error::Error;
+use crate::exergy::{gibbs_energy, helmholtz_energy};
+use crate::fluid::Fluid;
+use crate::math::ColumnTransform;
+use crate::metadata::TableMetadata;
+use crate::saturated_steam::{MixtureProperty, SaturatedProperty, SaturatedState, SteamTable};
+use crate::InterpolationMode;
 
+mod derived;
+mod flash;
+mod grid;
+mod phase;
+mod state;
 mod waterpoint;
+use flash::*;
+use grid::*;
+use state::*;
 use waterpoint::*;
 
+pub use derived::{derive_by_differences, DerivedProperties};
+pub use flash::FlashState;
+pub use phase::Phase;
+pub use state::SinglePhaseState;
+pub use waterpoint::{EstimatedWaterPoint, InterpolationMethod};
+
 /// Table to hold properties of water at different temperatures and pressure
 pub struct WaterTable {
+    metadata: TableMetadata,
     headers: Vec<String>,
+    /// index in `WaterPoint::values` of each `WaterProperty`, found from the headers
+    columns: Vec<usize>,
+    /// points of the table sorted by pressure then temperature
     value_points: Vec<WaterPoint>,
+    grid: WaterGrid,
+    /// transform of each value when interpolating, indexed like `WaterPoint::values`
+    transforms: Vec<ColumnTransform>,
+    saturation_table: SteamTable,
+    interpolation_mode: InterpolationMode,
 }
 
 impl WaterTable {
+    /// Creates a water table which uses the saturated steam table bundled for its fluid
+    /// for two-phase states
     pub fn new(data_table: String) -> Result<WaterTable, Error> {
-        let lines: Vec<String> = data_table.lines().map(|line| line.to_string()).collect();
+        let fluid = super::split_table(&data_table)?
+            .metadata
+            .fluid
+            .unwrap_or_default();
+        let saturation_table = SteamTable::bundled_by_pressure(fluid)?;
+
+        WaterTable::with_saturation_table(data_table, saturation_table)
+    }
 
-        let headers = super::get_headers_from_string(&lines[6]);
+    /// the water table bundled for `fluid`
+    pub fn bundled(fluid: Fluid) -> Result<WaterTable, Error> {
+        WaterTable::new(fluid.bundled_tables().water.to_string())
+    }
 
-        let value_lines = lines[7..].to_vec();
+    /// Creates a water table which uses `saturation_table` for two-phase states,
+    /// both tables must hold the same fluid
+    pub fn with_saturation_table(
+        data_table: String,
+        saturation_table: SteamTable,
+    ) -> Result<WaterTable, Error> {
+        let table_text = super::split_table(&data_table)?;
+        let fluid = table_text.metadata.fluid.unwrap_or_default();
+        if saturation_table.fluid() != fluid {
+            return Err(Error::table_parsing(
+                None,
+                None,
+                &saturation_table.fluid().to_string(),
+                &format!("saturation table does not hold {}", fluid),
+            ));
+        }
+        let headers = table_text.headers;
+        let columns = find_value_columns(&headers, table_text.header_line)?;
 
-        let value_points = parse_to_water_point_struct(value_lines)?;
+        let mut value_points = parse_to_water_point_struct(table_text.lines, headers.len())?;
+        value_points.sort_by(|a, b| {
+            a.point
+                .0
+                .total_cmp(&b.point.0)
+                .then(a.point.1.total_cmp(&b.point.1))
+        });
+        let grid = WaterGrid::new(&value_points);
+        let transforms = find_value_transforms(&columns, headers.len() - 3);
 
         Ok(WaterTable {
+            metadata: table_text.metadata,
             headers,
+            columns,
             value_points,
+            grid,
+            transforms,
+            saturation_table,
+            interpolation_mode: InterpolationMode::Linear,
         })
     }
 
+    /// Reads a water table from the file at `path`, using the bundled saturated steam table
+    pub fn from_path(path: impl AsRef<Path>) -> Result<WaterTable, Error> {
+        let file = File::open(path).map_err(Error::Io)?;
+        WaterTable::from_reader(file)
+    }
+
+    pub fn from_reader(reader: impl Read) -> Result<WaterTable, Error> {
+        WaterTable::new(super::read_table(reader)?)
+    }
+
+    /// metadata from the preamble of the table
+    pub fn metadata(&self) -> &TableMetadata {
+        &self.metadata
+    }
+
+    /// fluid named by the preamble of the table, water by default
+    pub fn fluid(&self) -> Fluid {
+        self.metadata.fluid.unwrap_or_default()
+    }
+
+    pub fn interpolation_mode(&self) -> InterpolationMode {
+        self.interpolation_mode
+    }
+
+    /// Sets how values between the points of the table are interpolated, `Linear` by default
+    ///
+    /// the other modes interpolate along the isobars and then across them. They fall back to
+    /// linear interpolation in cells crossed by the saturation line, where the isobars around
+    /// the query do not cover its temperature on its side of the saturation line, and in the
+    /// flashes. `Lagrange` with less than two nodes is rejected
+    pub fn set_interpolation_mode(
+        &mut self,
+        interpolation_mode: InterpolationMode,
+    ) -> Result<(), Error> {
+        if let InterpolationMode::Lagrange(0..=1) = interpolation_mode {
+            return Err(Error::UnsupportedInterpolationMode(interpolation_mode));
+        }
+
+        self.interpolation_mode = interpolation_mode;
+        Ok(())
+    }
+
     pub fn get_values_at_point(
         &self,
-        pressure: f32,
-        temperature: f32,
+        pressure: f64,
+        temperature: f64,
     ) -> Result<WaterPoint, Error> {
-        let pressure_bounds = self.find_pressure_bounds(pressure);
-        let temperature_bounds = self.find_temperature_bounds(temperature);
-
-        let water_point_0_0 = self.get_water_point(pressure_bounds.0, temperature_bounds.0)?;
-        let water_point_0_1 = self.get_water_point(pressure_bounds.0, temperature_bounds.1)?;
-        let water_point_1_0 = self.get_water_point(pressure_bounds.1, temperature_bounds.0)?;
-        let water_point_1_1 = self.get_water_point(pressure_bounds.1, temperature_bounds.1)?;
-
-        let interpolated_water_point = if !phase_change_occurs((
-            water_point_0_0.clone(),
-            water_point_0_1.clone(),
-            water_point_1_0.clone(),
-            water_point_1_1.clone(),
-        )) {
-            interpolate_water_points(
+        let water_points = self.get_cell(pressure, temperature)?;
+        let (water_point, _) = self.interpolate_cell(pressure, temperature, water_points)?;
+
+        Ok(water_point)
+    }
+
+    /// Interpolates like `get_values_at_point`, together with the corners of the cell
+    /// around the query and an estimate of the interpolation error of every value
+    pub fn get_estimated_values_at_point(
+        &self,
+        pressure: f64,
+        temperature: f64,
+    ) -> Result<EstimatedWaterPoint, Error> {
+        let water_points = self.get_cell(pressure, temperature)?;
+        let corners = [
+            water_points.0.point,
+            water_points.1.point,
+            water_points.2.point,
+            water_points.3.point,
+        ];
+        let cell_size = cell_size(&corners);
+
+        let (water_point, method) =
+            self.interpolate_cell(pressure, temperature, water_points.clone())?;
+
+        // the difference between linear and quadratic interpolation estimates the error
+        let errors = match method {
+            InterpolationMethod::Cell => {
+                let linear_point = interpolate_water_points(
+                    pressure,
+                    temperature,
+                    water_points.0,
+                    water_points.1,
+                    water_points.2,
+                    water_points.3,
+                    &self.transforms,
+                );
+
+                self.interpolate_with_mode(InterpolationMode::Lagrange(3), &linear_point)
+                    .map(|values| {
+                        values
+                            .iter()
+                            .zip(&linear_point.values)
+                            .map(|(quadratic, linear)| (quadratic - linear).abs())
+                            .collect()
+                    })
+            }
+            _ => None,
+        };
+
+        Ok(EstimatedWaterPoint {
+            water_point,
+            corners,
+            cell_size,
+            errors,
+            method,
+        })
+    }
+
+    /// Heat capacities, speed of sound, compressibility, expansion and Joule-Thomson
+    /// coefficients from differences across the cell of the table around the point
+    pub fn get_derived_properties_at_point(
+        &self,
+        pressure: f64,
+        temperature: f64,
+    ) -> Result<DerivedProperties, Error> {
+        let water_points = self.get_cell(pressure, temperature)?;
+        let (pressure_step, temperature_step) = cell_size(&[
+            water_points.0.point,
+            water_points.1.point,
+            water_points.2.point,
+            water_points.3.point,
+        ]);
+
+        derive_by_differences(
+            &InterpolatedStates(self),
+            pressure,
+            temperature,
+            pressure_step / 2.0,
+            temperature_step / 2.0,
+        )
+    }
+
+    /// Points of the table around the query as (lower isobar lower temperature,
+    /// lower isobar upper temperature, upper isobar lower temperature, upper isobar upper temperature)
+    fn get_cell(
+        &self,
+        pressure: f64,
+        temperature: f64,
+    ) -> Result<(WaterPoint, WaterPoint, WaterPoint, WaterPoint), Error> {
+        self.is_pressure_valid(pressure)?;
+
+        // each isobar has its own temperatures, so the cell is bounded on both isobars
+        let (lower_pressure, upper_pressure) = self.grid.pressure_cell(pressure);
+        let (water_point_0_0, water_point_0_1) =
+            self.get_isobar_bounds(lower_pressure, temperature)?;
+        let (water_point_1_0, water_point_1_1) =
+            self.get_isobar_bounds(upper_pressure, temperature)?;
+
+        Ok((
+            water_point_0_0,
+            water_point_0_1,
+            water_point_1_0,
+            water_point_1_1,
+        ))
+    }
+
+    fn interpolate_cell(
+        &self,
+        pressure: f64,
+        temperature: f64,
+        water_points: (WaterPoint, WaterPoint, WaterPoint, WaterPoint),
+    ) -> Result<(WaterPoint, InterpolationMethod), Error> {
+        if !phase_change_occurs(water_points.clone()) {
+            let water_point = interpolate_water_points(
                 pressure,
                 temperature,
-                water_point_0_0,
-                water_point_0_1,
-                water_point_1_0,
-                water_point_1_1,
+                water_points.0,
+                water_points.1,
+                water_points.2,
+                water_points.3,
+                &self.transforms,
+            );
+
+            let water_point = match self.interpolation_mode {
+                InterpolationMode::Linear => water_point,
+                mode => match self.interpolate_with_mode(mode, &water_point) {
+                    Some(values) => WaterPoint {
+                        values,
+                        ..water_point
+                    },
+                    None => water_point,
+                },
+            };
+
+            return Ok((water_point, InterpolationMethod::Cell));
+        }
+
+        match self.interpolate_across_saturation(pressure, temperature, &water_points) {
+            Ok(water_point) => Ok((water_point, InterpolationMethod::AcrossSaturation)),
+            // the saturation line is not known at this temperature
+            Err(Error::ValueOutOfRange(_, _)) => Ok((
+                get_nearest_water_point(pressure, temperature, water_points),
+                InterpolationMethod::NearestPoint,
+            )),
+            Err(error) => Err(error),
+        }
+    }
+
+    /// Interpolates the values of the point found by double linear interpolation again
+    /// with `mode`, first along the isobars around it then across them
+    ///
+    /// only the points on the same side of the saturation line are used, there are no
+    /// values when the isobars do not cover the temperature on that side
+    fn interpolate_with_mode(
+        &self,
+        mode: InterpolationMode,
+        linear_point: &WaterPoint,
+    ) -> Option<Vec<f64>> {
+        let (pressure, temperature) = linear_point.point;
+        let is_liquid = linear_point.phase.is_liquid();
+
+        let (lower_pressure, _) = self.grid.pressure_cell(pressure);
+        let isobar_states: Vec<WaterPoint> = mode
+            .window(lower_pressure, self.grid.pressures.len())
+            .filter_map(|index| {
+                self.interpolate_isobar_with_mode(mode, index, temperature, is_liquid)
+            })
+            .collect();
+
+        let is_bracketed = match (isobar_states.first(), isobar_states.last()) {
+            (Some(first), Some(last)) => first.point.0 <= pressure && pressure <= last.point.0,
+            _ => false,
+        };
+        if isobar_states.len() < 2 || !is_bracketed {
+            return None;
+        }
+
+        let isobar_states: Vec<&WaterPoint> = isobar_states.iter().collect();
+        Some(interpolate_values_with_mode(
+            mode,
+            pressure,
+            &isobar_states,
+            |water_point| water_point.point.0,
+            &self.transforms,
+        ))
+    }
+
+    /// State at `temperature` on the isobar at `pressure_index` interpolated with
+    /// `mode` through the points on the liquid or vapor side only
+    fn interpolate_isobar_with_mode(
+        &self,
+        mode: InterpolationMode,
+        pressure_index: usize,
+        temperature: f64,
+        is_liquid: bool,
+    ) -> Option<WaterPoint> {
+        let nodes: Vec<&WaterPoint> = self
+            .get_isobar(pressure_index)
+            .iter()
+            .filter(|water_point| water_point.phase.is_liquid() == is_liquid)
+            .collect();
+
+        let (first, last) = (nodes.first()?, nodes.last()?);
+        if nodes.len() < 2 || !(first.point.1..=last.point.1).contains(&temperature) {
+            return None;
+        }
+
+        let upper = nodes
+            .partition_point(|water_point| water_point.point.1 <= temperature)
+            .clamp(1, nodes.len() - 1);
+        let window = mode.window(upper - 1, nodes.len());
+
+        let values = interpolate_values_with_mode(
+            mode,
+            temperature,
+            &nodes[window],
+            |water_point| water_point.point.1,
+            &self.transforms,
+        );
+
+        Some(WaterPoint {
+            point: (first.point.0, temperature),
+            values,
+            phase: first.phase,
+        })
+    }
+
+    /// Interpolates in a cell crossed by the saturation line
+    ///
+    /// the cell is split at the saturation pressure of `temperature` and only the side holding
+    /// the state is interpolated, between its isobar and the saturated liquid or vapor state
+    fn interpolate_across_saturation(
+        &self,
+        pressure: f64,
+        temperature: f64,
+        water_points: &(WaterPoint, WaterPoint, WaterPoint, WaterPoint),
+    ) -> Result<WaterPoint, Error> {
+        let lower_state = interpolate_along_isobar(temperature, &water_points.0, &water_points.1);
+        let upper_state = interpolate_along_isobar(temperature, &water_points.2, &water_points.3);
+
+        let saturated_state = self
+            .saturation_table
+            .get_state_at_property(SaturatedProperty::Temperature, temperature)?;
+
+        let (liquid_state, vapor_state) = if lower_state.phase.is_liquid() {
+            (lower_state, upper_state)
+        } else {
+            (upper_state, lower_state)
+        };
+
+        // liquid lies above the saturation pressure and vapor below it
+        let water_point = if pressure >= saturated_state.pressure {
+            let saturated_liquid =
+                self.saturated_water_point(&saturated_state, Phase::SaturatedLiquid);
+            interpolate_between_isobars(pressure, saturated_liquid, liquid_state)
+        } else {
+            let saturated_vapor =
+                self.saturated_water_point(&saturated_state, Phase::SaturatedVapor);
+            interpolate_between_isobars(pressure, saturated_vapor, vapor_state)
+        };
+
+        Ok(water_point)
+    }
+
+    /// Point of saturated liquid or saturated vapor with the value columns of the table
+    fn saturated_water_point(&self, saturated_state: &SaturatedState, phase: Phase) -> WaterPoint {
+        let (specific_volume, internal_energy, enthalpy, entropy) = if phase.is_liquid() {
+            (
+                saturated_state.specific_volume_liquid,
+                saturated_state.internal_energy_liquid,
+                saturated_state.enthalpy_liquid,
+                saturated_state.entropy_liquid,
             )
         } else {
-            get_nearest_water_point(
-                pressure,
-                temperature,
-                (
-                    water_point_0_0,
-                    water_point_0_1,
-                    water_point_1_0,
-                    water_point_1_1,
-                ),
+            (
+                saturated_state.specific_volume_vapor,
+                saturated_state.internal_energy_vapor,
+                saturated_state.enthalpy_vapor,
+                saturated_state.entropy_vapor,
             )
         };
 
-        Ok(interpolated_water_point)
+        let mut values = vec![0.0; self.value_points[0].values.len()];
+        values[self.value_index(WaterProperty::SpecificVolume)] = specific_volume;
+        values[self.value_index(WaterProperty::Density)] = 1.0 / specific_volume;
+        values[self.value_index(WaterProperty::InternalEnergy)] = internal_energy;
+        values[self.value_index(WaterProperty::Enthalpy)] = enthalpy;
+        values[self.value_index(WaterProperty::Entropy)] = entropy;
+
+        WaterPoint {
+            point: (saturated_state.pressure, saturated_state.temperature),
+            values,
+            phase,
+        }
     }
 
     pub fn get_labelled_values_at_point(
         &self,
-        pressure: f32,
-        temperature: f32,
+        pressure: f64,
+        temperature: f64,
     ) -> Result<Vec<(String, String)>, Error> {
         let interpolated_water_point = self.get_values_at_point(pressure, temperature)?;
 
         Ok(self.convert_water_point_to_labelled_data(interpolated_water_point))
     }
 
-    fn find_pressure_bounds(&self, target: f32) -> (f32, f32) {
-        let mut lower_bound = self.get_minimum_allowable_pressure();
-        let mut upper_bound = self.get_maximum_allowable_pressure();
+    pub fn get_state_at_point(
+        &self,
+        pressure: f64,
+        temperature: f64,
+    ) -> Result<SinglePhaseState, Error> {
+        let water_point = self.get_values_at_point(pressure, temperature)?;
+        Ok(self.state_from_water_point(&water_point))
+    }
+
+    /// State like `get_state_at_point` which also holds the properties derived across
+    /// the table around it, none where the table does not extend around the point
+    ///
+    /// deriving them interpolates the states around the point, which takes several times
+    /// as long as the lookup of the state itself
+    pub fn get_state_with_derived_properties_at_point(
+        &self,
+        pressure: f64,
+        temperature: f64,
+    ) -> Result<SinglePhaseState, Error> {
+        let state = self.get_state_at_point(pressure, temperature)?;
+
+        Ok(SinglePhaseState {
+            derived_properties: self
+                .get_derived_properties_at_point(pressure, temperature)
+                .ok(),
+            ..state
+        })
+    }
+
+    fn state_from_water_point(&self, water_point: &WaterPoint) -> SinglePhaseState {
+        let value = |property: WaterProperty| water_point.values[self.value_index(property)];
+
+        let temperature = water_point.point.1;
+        let internal_energy = value(WaterProperty::InternalEnergy);
+        let enthalpy = value(WaterProperty::Enthalpy);
+        let entropy = value(WaterProperty::Entropy);
+
+        SinglePhaseState {
+            pressure: water_point.point.0,
+            temperature,
+            specific_volume: value(WaterProperty::SpecificVolume),
+            density: value(WaterProperty::Density),
+            internal_energy,
+            enthalpy,
+            entropy,
+            gibbs_energy: gibbs_energy(enthalpy, entropy, temperature),
+            helmholtz_energy: helmholtz_energy(internal_energy, entropy, temperature),
+            phase: water_point.phase,
+            derived_properties: None,
+        }
+    }
+
+    fn value_index(&self, property: WaterProperty) -> usize {
+        self.columns[property as usize]
+    }
+
+    /// Finds the state of water at `pressure` with specific enthalpy `enthalpy`
+    ///
+    /// when the enthalpy lies between that of saturated liquid and saturated vapor
+    /// the state is a two-phase mixture and its quality is returned
+    pub fn get_values_at_pressure_enthalpy(
+        &self,
+        pressure: f64,
+        enthalpy: f64,
+    ) -> Result<FlashState, Error> {
+        self.flash_at_property(pressure, MixtureProperty::Enthalpy, enthalpy)
+    }
+
+    /// Finds the state of water at `pressure` with specific entropy `entropy`
+    ///
+    /// used for isentropic processes, when the entropy lies between that of saturated liquid
+    /// and saturated vapor the state is a two-phase mixture and its quality is returned
+    pub fn get_values_at_pressure_entropy(
+        &self,
+        pressure: f64,
+        entropy: f64,
+    ) -> Result<FlashState, Error> {
+        self.flash_at_property(pressure, MixtureProperty::Entropy, entropy)
+    }
+
+    fn flash_at_property(
+        &self,
+        pressure: f64,
+        property: MixtureProperty,
+        value: f64,
+    ) -> Result<FlashState, Error> {
+        self.is_pressure_valid(pressure)?;
+
+        let (lower_pressure, upper_pressure) = self.grid.pressure_cell(pressure);
+        let value_index = self.value_index(WaterProperty::from(property));
+
+        if !value.is_finite() {
+            let isobar = self.get_isobar(lower_pressure);
+            return Err(Error::ValueOutOfRange(
+                isobar[0].values[value_index],
+                isobar[isobar.len() - 1].values[value_index],
+            ));
+        }
 
-        for value_point in &self.value_points {
-            if value_point.point.0 <= target {
-                lower_bound = value_point.point.0;
-            } else if value_point.point.0 > target {
-                upper_bound = value_point.point.0;
-                break;
+        let saturation_point = match self.get_saturation_point(pressure) {
+            Ok(point) => Some(point),
+            // the pressure is above the critical point
+            Err(Error::ValueOutOfRange(_, _)) => None,
+            Err(error) => return Err(error),
+        };
+
+        if let Some(point) = saturation_point {
+            match self
+                .saturation_table
+                .get_mixture_at_property(point, property, value)
+            {
+                Ok(mixture) => return Ok(FlashState::TwoPhase(mixture)),
+                // the state is not saturated, or the pressure is above the critical point
+                Err(Error::ValueOutOfRange(_, _)) => {}
+                Err(error) => return Err(error),
             }
         }
 
-        (upper_bound, lower_bound)
+        // the side of the saturation line holding the state, none above the critical point
+        let liquid_side = saturation_point
+            .and_then(|point| {
+                self.saturation_table
+                    .get_mixture_at_quality(point, 0.0)
+                    .ok()
+            })
+            .map(|saturated_liquid| value < saturated_liquid.value(property));
+
+        let lower_state = interpolate_isobar_side_at_value(
+            self.get_isobar(lower_pressure),
+            value_index,
+            value,
+            liquid_side,
+        )?;
+        let upper_state = interpolate_isobar_side_at_value(
+            self.get_isobar(upper_pressure),
+            value_index,
+            value,
+            liquid_side,
+        )?;
+
+        let water_point = interpolate_between_isobars(pressure, lower_state, upper_state);
+
+        Ok(FlashState::SinglePhase(
+            self.state_from_water_point(&water_point),
+        ))
     }
 
-    fn get_water_point(&self, pressure: f32, temperature: f32) -> Result<WaterPoint, Error> {
-        let water_point = self.value_points.iter().find(|value_point| {
-            value_point.point.0 == pressure && value_point.point.1 == temperature
-        });
+    /// saturated steam table used for two-phase states
+    pub fn saturation_table(&self) -> &SteamTable {
+        &self.saturation_table
+    }
 
-        if let Some(point) = water_point {
-            Ok(point.clone())
+    /// point of the saturation table corresponding to `pressure`
+    fn get_saturation_point(&self, pressure: f64) -> Result<f64, Error> {
+        if self.saturation_table.is_temperature_indexed() {
+            let data_point = self
+                .saturation_table
+                .get_values_at_property(SaturatedProperty::Pressure, pressure)?;
+            Ok(data_point.point)
         } else {
-            let err_str = format!(
-                "Could not find point corresponding to P = {}, T = {}",
-                pressure, temperature
-            );
-            Err(Error::TableParsingError(err_str))
+            Ok(pressure)
         }
     }
 
-    fn find_temperature_bounds(&self, target: f32) -> (f32, f32) {
-        let mut lower_bound = self.get_minimum_allowable_temperature();
-        let mut upper_bound = self.get_maximum_allowable_temperature();
+    /// all points of the table on the isobar at `pressure_index` sorted by temperature
+    fn get_isobar(&self, pressure_index: usize) -> &[WaterPoint] {
+        &self.value_points[self.grid.isobar(pressure_index)]
+    }
 
-        for value_point in &self.value_points {
-            if value_point.point.1 <= target {
-                lower_bound = value_point.point.1;
-            } else if value_point.point.1 > target {
-                upper_bound = value_point.point.1;
-                break;
-            }
+    pub fn is_pressure_valid(&self, pressure: f64) -> Result<(), Error> {
+        let minimum_pressure = self.get_minimum_allowable_pressure();
+        let maximum_pressure = self.get_maximum_allowable_pressure();
+
+        if !(minimum_pressure..=maximum_pressure).contains(&pressure) {
+            return Err(Error::ValueOutOfRange(minimum_pressure, maximum_pressure));
         }
 
-        (upper_bound, lower_bound)
+        Ok(())
+    }
+
+    /// points of the isobar at `pressure_index` on either side of `temperature`
+    fn get_isobar_bounds(
+        &self,
+        pressure_index: usize,
+        temperature: f64,
+    ) -> Result<(WaterPoint, WaterPoint), Error> {
+        let isobar = self.get_isobar(pressure_index);
+        let (lower, upper) = isobar_temperature_bounds(isobar, temperature)?;
+
+        Ok((isobar[lower].clone(), isobar[upper].clone()))
     }
 
-    pub fn get_minimum_allowable_pressure(&self) -> f32 {
-        self.value_points[0].point.0
+    pub fn get_minimum_allowable_pressure(&self) -> f64 {
+        self.grid.pressures[0]
     }
 
-    pub fn get_maximum_allowable_pressure(&self) -> f32 {
-        let value_length = self.value_points.len();
-        self.value_points[value_length - 1].point.0
+    pub fn get_maximum_allowable_pressure(&self) -> f64 {
+        self.grid.pressures[self.grid.pressures.len() - 1]
     }
 
-    pub fn get_minimum_allowable_temperature(&self) -> f32 {
-        self.value_points[0].point.1
+    pub fn get_minimum_allowable_temperature(&self) -> f64 {
+        self.grid.temperature_range.0
     }
 
-    pub fn get_maximum_allowable_temperature(&self) -> f32 {
-        let value_length = self.value_points.len();
-        self.value_points[value_length - 1].point.1
+    pub fn get_maximum_allowable_temperature(&self) -> f64 {
+        self.grid.temperature_range.1
     }
 
     fn convert_water_point_to_labelled_data(
@@ -164,8 +682,554 @@ impl WaterTable {
             labelled_data.push((headers.next().unwrap().to_owned(), value.to_string()));
         });
 
-        labelled_data.push((headers.next().unwrap().to_owned(), waterpoint.phase));
+        labelled_data.push((
+            headers.next().unwrap().to_owned(),
+            waterpoint.phase.to_string(),
+        ));
 
         labelled_data
     }
 }
+
+/// States of the water table without their derived properties,
+/// which are found from differences of these
+struct InterpolatedStates<'a>(&'a WaterTable);
+
+impl SinglePhaseProperties for InterpolatedStates<'_> {
+    fn single_phase_state(
+        &self,
+        pressure: f64,
+        temperature: f64,
+    ) -> Result<SinglePhaseState, Error> {
+        let water_point = self.0.get_values_at_point(pressure, temperature)?;
+        Ok(self.0.state_from_water_point(&water_point))
+    }
+}
+
+/// Pressure and largest temperature spacing of the cell with `corners`
+/// ordered like the points returned by `get_cell`
+fn cell_size(corners: &[(f64, f64); 4]) -> (f64, f64) {
+    (
+        corners[2].0 - corners[0].0,
+        (corners[1].1 - corners[0].1).max(corners[3].1 - corners[2].1),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data;
+
+    fn water_table() -> WaterTable {
+        WaterTable::new(data::COMPRESSED_LIQUID_SUPERHEATED_STEAM.to_string()).unwrap()
+    }
+
+    #[test]
+    fn test_get_state_at_point() {
+        let state = water_table().get_state_at_point(1.0, 300.0).unwrap();
+
+        assert_eq!(state.pressure, 1.0);
+        assert_eq!(state.temperature, 300.0);
+        assert_eq!(state.specific_volume, 0.25799);
+        assert_eq!(state.density, 3.8762);
+        assert_eq!(state.internal_energy, 2793.61);
+        assert_eq!(state.enthalpy, 3051.6);
+        assert_eq!(state.entropy, 7.1246);
+        assert_eq!(state.phase, Phase::SuperheatedVapor);
+    }
+
+    #[test]
+    fn test_get_state_at_point_not_a_number() {
+        let water_table = water_table();
+
+        assert!(matches!(
+            water_table.get_state_at_point(f64::NAN, 300.0),
+            Err(Error::ValueOutOfRange(_, _))
+        ));
+        assert!(matches!(
+            water_table.get_state_at_point(1.0, f64::NAN),
+            Err(Error::ValueOutOfRange(_, _))
+        ));
+    }
+
+    #[test]
+    fn test_energy_balance() {
+        let water_table = water_table();
+
+        // h = u + P v with P in MPa and v in m³/kg giving kJ/kg, up to 7717.5 kJ/kg
+        for water_point in &water_table.value_points {
+            let state = water_table.state_from_water_point(water_point);
+            let flow_work = 1.0e3 * state.pressure * state.specific_volume;
+            let imbalance = state.enthalpy - state.internal_energy - flow_work;
+            assert!(imbalance.abs() < 0.1, "{:?}", state);
+        }
+
+        // flashes return the enthalpy they were given
+        for pressure in [0.01, 1.0, 10.0, 50.0] {
+            for enthalpy in [100.0, 1500.0, 2800.0, 3500.0, 6374.7] {
+                let state = water_table
+                    .get_values_at_pressure_enthalpy(pressure, enthalpy)
+                    .unwrap();
+                assert!((state.enthalpy() - enthalpy).abs() < 0.1, "{:?}", state);
+            }
+        }
+    }
+
+    #[test]
+    fn test_get_state_at_point_sparse_isobars() {
+        let water_table = water_table();
+
+        // the 800 MPa isobar starts at 20 °C and the 900 MPa isobar at 25 °C
+        let state = water_table.get_state_at_point(850.0, 25.0).unwrap();
+        assert!((state.enthalpy - (734.24 + 806.06) / 2.0).abs() < 1e-3);
+
+        let state = water_table.get_state_at_point(850.0, 27.5).unwrap();
+        let expected_enthalpy = (734.24 + 753.09 + 806.06 + 824.91) / 4.0;
+        assert!((state.enthalpy - expected_enthalpy).abs() < 1e-3);
+
+        assert!(matches!(
+            water_table.get_state_at_point(850.0, 22.5),
+            Err(Error::ValueOutOfRange(25.0, 2000.0))
+        ));
+    }
+
+    #[test]
+    fn test_get_state_at_point_across_saturation() {
+        let water_table = water_table();
+
+        // the saturation pressure at 180 °C is 1.0028 MPa, between the 1 and 1.1 MPa isobars
+        let state = water_table.get_state_at_point(1.05, 180.0).unwrap();
+        assert_eq!(state.phase, Phase::CompressedLiquid);
+        assert!((state.enthalpy - 763.075).abs() < 1e-2);
+        assert!((state.specific_volume - 0.0011274).abs() < 1e-6);
+
+        let state = water_table.get_state_at_point(1.001, 180.0).unwrap();
+        assert_eq!(state.phase, Phase::SuperheatedVapor);
+        assert!((state.enthalpy - 2777.2).abs() < 0.2);
+        assert!((state.specific_volume - 0.1941).abs() < 1e-3);
+    }
+
+    #[test]
+    fn test_get_values_at_pressure_enthalpy_superheated() {
+        let water_table = water_table();
+
+        let state = water_table
+            .get_values_at_pressure_enthalpy(1.0, 3051.6)
+            .unwrap();
+        assert_eq!(state.quality(), None);
+        assert!((state.temperature() - 300.0).abs() < 1e-3);
+        assert_eq!(state.phase(), Phase::SuperheatedVapor);
+
+        // halfway between the 1 MPa and 1.1 MPa isobars at 300 °C
+        let state = water_table
+            .get_values_at_pressure_enthalpy(1.05, 3050.3)
+            .unwrap();
+        assert!((state.temperature() - 300.0).abs() < 0.1);
+    }
+
+    #[test]
+    fn test_get_values_at_pressure_enthalpy_compressed_liquid() {
+        let state = water_table()
+            .get_values_at_pressure_enthalpy(10.0, 426.62)
+            .unwrap();
+        assert!((state.temperature() - 100.0).abs() < 1e-3);
+        assert_eq!(state.phase(), Phase::CompressedLiquid);
+    }
+
+    #[test]
+    fn test_get_values_at_pressure_enthalpy_two_phase() {
+        let state = water_table()
+            .get_values_at_pressure_enthalpy(1.0, 762.52 + 0.25 * 2014.6)
+            .unwrap();
+        assert!((state.quality().unwrap() - 0.25).abs() < 1e-4);
+        assert_eq!(state.phase(), Phase::TwoPhase);
+        assert_eq!((state.pressure(), state.temperature()), (1.0, 179.878));
+    }
+
+    #[test]
+    fn test_get_values_at_pressure_enthalpy_near_saturation() {
+        let water_table = water_table();
+        let saturated = water_table
+            .saturation_table()
+            .get_mixture_at_quality(1.05, 0.0)
+            .unwrap();
+        let saturated_vapor = water_table
+            .saturation_table()
+            .get_mixture_at_quality(1.05, 1.0)
+            .unwrap();
+
+        // the 1 MPa isobar saturates below 771 kJ/kg, which has to stay liquid
+        // rather than be bounded by its saturated liquid and vapor rows
+        let state = water_table
+            .get_values_at_pressure_enthalpy(1.05, saturated.enthalpy - 1.0)
+            .unwrap();
+        assert_eq!(state.phase(), Phase::CompressedLiquid);
+        assert!(state.temperature() < saturated.temperature);
+        assert!((state.temperature() - 181.8).abs() < 0.1);
+        assert!((state.specific_volume() - 0.00113).abs() < 1e-5);
+
+        let state = water_table
+            .get_values_at_pressure_enthalpy(1.05, saturated.enthalpy + 1.0)
+            .unwrap();
+        assert_eq!(state.phase(), Phase::TwoPhase);
+        assert!(state.quality().unwrap() < 1e-3);
+
+        let state = water_table
+            .get_values_at_pressure_enthalpy(1.05, saturated_vapor.enthalpy - 1.0)
+            .unwrap();
+        assert_eq!(state.phase(), Phase::TwoPhase);
+        assert!(state.quality().unwrap() > 0.999);
+
+        let state = water_table
+            .get_values_at_pressure_enthalpy(1.05, saturated_vapor.enthalpy + 1.0)
+            .unwrap();
+        assert_eq!(state.phase(), Phase::SuperheatedVapor);
+        assert!(state.temperature() > saturated.temperature);
+        assert!(state.temperature() - saturated.temperature < 1.0);
+        assert!((state.specific_volume() - saturated_vapor.specific_volume).abs() < 1e-3);
+    }
+
+    #[test]
+    fn test_get_values_at_pressure_entropy() {
+        let water_table = water_table();
+
+        let state = water_table
+            .get_values_at_pressure_entropy(1.0, 7.1246)
+            .unwrap();
+        assert_eq!(state.quality(), None);
+        assert!((state.temperature() - 300.0).abs() < 1e-3);
+
+        // isentropic expansion of 1 MPa, 300 °C steam down to 0.1 MPa ends up as wet steam
+        let state = water_table
+            .get_values_at_pressure_entropy(0.1, 7.1246)
+            .unwrap();
+        let quality = (7.1246 - 1.3028) / (7.3588 - 1.3028);
+        assert!((state.quality().unwrap() - quality).abs() < 1e-4);
+        assert_eq!(state.temperature(), 99.606);
+    }
+
+    #[test]
+    fn test_get_values_at_pressure_entropy_near_saturation() {
+        let water_table = water_table();
+        let saturated = water_table
+            .saturation_table()
+            .get_mixture_at_quality(1.05, 0.0)
+            .unwrap();
+        let saturated_vapor = water_table
+            .saturation_table()
+            .get_mixture_at_quality(1.05, 1.0)
+            .unwrap();
+
+        // 2.15 kJ/(kg K) lies past the saturated liquid of the 1 MPa isobar
+        let state = water_table
+            .get_values_at_pressure_entropy(1.05, 2.15)
+            .unwrap();
+        assert_eq!(state.phase(), Phase::CompressedLiquid);
+        assert!(state.temperature() < saturated.temperature);
+        assert!((state.specific_volume() - 0.00113).abs() < 1e-5);
+
+        let state = water_table
+            .get_values_at_pressure_entropy(1.05, saturated.entropy + 0.01)
+            .unwrap();
+        assert_eq!(state.phase(), Phase::TwoPhase);
+        assert!(state.quality().unwrap() < 0.01);
+
+        let state = water_table
+            .get_values_at_pressure_entropy(1.05, saturated_vapor.entropy - 0.01)
+            .unwrap();
+        assert_eq!(state.phase(), Phase::TwoPhase);
+        assert!(state.quality().unwrap() > 0.99);
+
+        let state = water_table
+            .get_values_at_pressure_entropy(1.05, saturated_vapor.entropy + 0.01)
+            .unwrap();
+        assert_eq!(state.phase(), Phase::SuperheatedVapor);
+        assert!(state.temperature() > saturated.temperature);
+        assert!((state.specific_volume() - saturated_vapor.specific_volume).abs() < 5e-3);
+    }
+
+    #[test]
+    fn test_get_values_at_pressure_enthalpy_out_of_range() {
+        let water_table = water_table();
+
+        assert!(water_table
+            .get_values_at_pressure_enthalpy(2000.0, 3000.0)
+            .is_err());
+        assert!(water_table
+            .get_values_at_pressure_enthalpy(1.0, 10000.0)
+            .is_err());
+        assert!(matches!(
+            water_table.get_values_at_pressure_enthalpy(1.0, f64::NAN),
+            Err(Error::ValueOutOfRange(_, _))
+        ));
+        assert!(matches!(
+            water_table.get_values_at_pressure_entropy(1.0, f64::INFINITY),
+            Err(Error::ValueOutOfRange(_, _))
+        ));
+    }
+
+    #[test]
+    fn test_broken_table() {
+        let table = data::COMPRESSED_LIQUID_SUPERHEATED_STEAM.replacen(
+            "700, 10, 0.00083548",
+            "700, 0, 0, , 0, , , \"liquid\"\n700, 10, 0.00083548",
+            1,
+        );
+
+        match WaterTable::new(table) {
+            Err(Error::TableParsingError(error)) => {
+                assert_eq!(error.column, Some(4));
+                assert_eq!(error.text, "");
+            }
+            _ => panic!("broken table should not parse"),
+        }
+    }
+
+    #[test]
+    fn test_from_path() {
+        let path = concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/src/data/compressed_liquid_and_superheated_steam.csv"
+        );
+        let loaded_table = WaterTable::from_path(path).unwrap();
+
+        assert_eq!(loaded_table.metadata().version.as_deref(), Some("1.2"));
+        assert_eq!(loaded_table.metadata().date.as_deref(), Some("4/15/2022"));
+        assert_eq!(
+            loaded_table.get_state_at_point(1.0, 300.0).unwrap(),
+            water_table().get_state_at_point(1.0, 300.0).unwrap()
+        );
+
+        assert!(matches!(
+            WaterTable::from_path("missing_table.csv"),
+            Err(Error::Io(_))
+        ));
+    }
+
+    #[test]
+    fn test_fluid_of_tables() {
+        assert_eq!(water_table().fluid(), Fluid::Water);
+        assert_eq!(
+            WaterTable::bundled(Fluid::Water).unwrap().fluid(),
+            Fluid::Water
+        );
+
+        // the saturation table follows the fluid of the table
+        let heavy_water_table = WaterTable::bundled(Fluid::HeavyWater).unwrap();
+        assert_eq!(heavy_water_table.fluid(), Fluid::HeavyWater);
+        assert_eq!(
+            heavy_water_table.saturation_table().fluid(),
+            Fluid::HeavyWater
+        );
+
+        let water_data = data::HEAVY_WATER_COMPRESSED_LIQUID_SUPERHEATED_VAPOR.to_string();
+        assert!(matches!(
+            WaterTable::with_saturation_table(
+                water_data.clone(),
+                SteamTable::bundled_by_pressure(Fluid::Water).unwrap()
+            ),
+            Err(Error::TableParsingError(_))
+        ));
+        assert!(WaterTable::with_saturation_table(
+            water_data,
+            SteamTable::bundled_by_pressure(Fluid::HeavyWater).unwrap()
+        )
+        .is_ok());
+    }
+
+    #[test]
+    fn test_heavy_water_table() {
+        let water_table = WaterTable::bundled(Fluid::HeavyWater).unwrap();
+
+        // liquid density of 1104.4 kg/m^3 at 25 °C
+        let liquid = water_table.get_state_at_point(0.1, 25.0).unwrap();
+        assert!((liquid.density - 1104.4).abs() / 1104.4 < 5e-3);
+        assert_eq!(liquid.phase, Phase::CompressedLiquid);
+
+        // boiling at 0.1 MPa near 101.0 °C
+        let saturation = water_table
+            .saturation_table()
+            .get_state_at_point(0.1)
+            .unwrap();
+        assert!((saturation.temperature - 101.04).abs() < 0.1);
+        assert_eq!(
+            water_table.get_state_at_point(0.1, 105.0).unwrap().phase,
+            Phase::SuperheatedVapor
+        );
+
+        let enthalpy = 0.5 * (saturation.enthalpy_liquid + saturation.enthalpy_vapor);
+        let flash = water_table
+            .get_values_at_pressure_enthalpy(0.1, enthalpy)
+            .unwrap();
+        assert!((flash.quality().unwrap() - 0.5).abs() < 1e-6);
+
+        let supercritical = water_table.get_state_at_point(25.0, 400.0).unwrap();
+        assert_eq!(supercritical.phase, Phase::Supercritical);
+
+        assert!(matches!(
+            water_table.transport_properties(0.1, 25.0),
+            Err(Error::UnsupportedFluid(Fluid::HeavyWater))
+        ));
+    }
+
+    #[test]
+    fn test_get_estimated_values_at_point() {
+        let water_table = water_table();
+
+        let estimate = water_table
+            .get_estimated_values_at_point(0.015, 150.0)
+            .unwrap();
+        assert_eq!(estimate.method, InterpolationMethod::Cell);
+        assert_eq!(estimate.corners[0], (0.01, 150.0));
+        assert_eq!(estimate.corners[3], (0.02, 155.0));
+        assert_eq!(estimate.cell_size, (0.01, 5.0));
+        let errors = estimate.errors.unwrap();
+        assert!(errors
+            .iter()
+            .all(|error| error.is_finite() && *error >= 0.0));
+
+        let estimate = water_table
+            .get_estimated_values_at_point(1.05, 180.0)
+            .unwrap();
+        assert_eq!(estimate.method, InterpolationMethod::AcrossSaturation);
+        assert!(estimate.errors.is_none());
+
+        // without saturation data at 180 °C the cell falls back to its nearest point
+        let saturated_rows: String = data::SATURATED_BY_PRESSURE_TABLE
+            .lines()
+            .filter(|line| line.split(',').next().unwrap().parse().unwrap_or(0.0) < 0.5)
+            .map(|line| format!("{}\n", line))
+            .collect();
+        let saturation_table = SteamTable::new(saturated_rows).unwrap();
+        let water_table = WaterTable::with_saturation_table(
+            data::COMPRESSED_LIQUID_SUPERHEATED_STEAM.to_string(),
+            saturation_table,
+        )
+        .unwrap();
+
+        let estimate = water_table
+            .get_estimated_values_at_point(1.07, 180.0)
+            .unwrap();
+        assert!(estimate.is_nearest_point());
+        assert_eq!(estimate.water_point.point, (1.1, 180.0));
+    }
+
+    #[test]
+    fn test_get_derived_properties_at_point() {
+        use crate::backend::SinglePhaseProperties;
+
+        let if97 = crate::if97::If97::new();
+        let water_table = water_table();
+
+        for (pressure, temperature) in [(1.05, 302.5), (0.015, 152.5), (10.5, 102.5)] {
+            let properties = water_table
+                .get_derived_properties_at_point(pressure, temperature)
+                .unwrap();
+            let expected = if97.derived_properties(pressure, temperature).unwrap();
+
+            let relative_error = |value: f64, expected: f64| ((value - expected) / expected).abs();
+            assert!(
+                relative_error(
+                    properties.isobaric_heat_capacity,
+                    expected.isobaric_heat_capacity
+                ) < 1e-2
+            );
+            assert!(relative_error(properties.speed_of_sound, expected.speed_of_sound) < 5e-2);
+            assert!(
+                relative_error(
+                    properties.volumetric_expansion_coefficient,
+                    expected.volumetric_expansion_coefficient
+                ) < 5e-2
+            );
+        }
+
+        // states only carry the derived properties when asked for them
+        let state = water_table.get_state_at_point(1.05, 302.5).unwrap();
+        assert_eq!(state.derived_properties, None);
+        let state = water_table
+            .get_state_with_derived_properties_at_point(1.05, 302.5)
+            .unwrap();
+        assert_eq!(
+            state.derived_properties,
+            water_table
+                .get_derived_properties_at_point(1.05, 302.5)
+                .ok()
+        );
+        let properties = state.derived_properties.unwrap();
+        assert!((properties.isobaric_heat_capacity - 2.3).abs() < 0.3);
+
+        match water_table
+            .get_values_at_pressure_enthalpy(1.05, state.enthalpy)
+            .unwrap()
+        {
+            FlashState::SinglePhase(flashed) => assert_eq!(flashed.derived_properties, None),
+            flash => panic!("{:?}", flash),
+        }
+
+        // next to the saturation line the differences stay on the side of the state
+        let properties = water_table
+            .get_derived_properties_at_point(1.0, 181.0)
+            .unwrap();
+        assert!(properties.isobaric_heat_capacity > 2.0);
+        assert!(properties.isobaric_heat_capacity < 3.5);
+    }
+
+    #[test]
+    fn test_specific_volume_transform() {
+        use crate::backend::SinglePhaseProperties;
+
+        let if97 = crate::if97::If97::new();
+        let water_table = water_table();
+
+        // halfway between two low pressure isobars the specific volume of the vapor is far from
+        // the mean of its corners, while the density is close to theirs
+        let state = water_table.get_state_at_point(0.015, 150.0).unwrap();
+        let expected = if97.single_phase_state(0.015, 150.0).unwrap();
+        let error = (state.specific_volume - expected.specific_volume) / expected.specific_volume;
+        assert!(error.abs() < 1e-3);
+
+        let state = water_table.get_state_at_point(0.01, 150.0).unwrap();
+        assert_eq!(state.specific_volume, 19.513);
+    }
+
+    #[test]
+    fn test_interpolation_modes() {
+        use crate::backend::SinglePhaseProperties;
+
+        let if97 = crate::if97::If97::new();
+        let mut water_table = water_table();
+
+        let points = [(0.015, 97.0), (0.33, 477.0), (2.37, 730.0), (7.7, 1300.0)];
+        let total_error = |water_table: &WaterTable| -> f64 {
+            points
+                .iter()
+                .map(|(pressure, temperature)| {
+                    let state = water_table.get_state_at_point(*pressure, *temperature);
+                    let expected = if97.single_phase_state(*pressure, *temperature).unwrap();
+                    let specific_volume = state.unwrap().specific_volume;
+                    ((specific_volume - expected.specific_volume) / expected.specific_volume).abs()
+                })
+                .sum()
+        };
+
+        let linear_error = total_error(&water_table);
+
+        for mode in [
+            InterpolationMode::NaturalCubicSpline,
+            InterpolationMode::MonotoneCubic,
+            InterpolationMode::Lagrange(4),
+            InterpolationMode::Bicubic,
+        ] {
+            water_table.set_interpolation_mode(mode).unwrap();
+            assert!(total_error(&water_table) < linear_error * 0.6);
+
+            let state = water_table.get_state_at_point(1.0, 300.0).unwrap();
+            assert_eq!(state.specific_volume, 0.25799);
+        }
+
+        assert!(matches!(
+            water_table.set_interpolation_mode(InterpolationMode::Lagrange(0)),
+            Err(Error::UnsupportedInterpolationMode(_))
+        ));
+    }
+}
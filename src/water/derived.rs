@@ -0,0 +1,127 @@
+use super::state::SinglePhaseState;
+use crate::backend::SinglePhaseProperties;
+use crate::constants::KELVIN_OFFSET;
+use crate::error::Error;
+
+/// Properties of a single phase state derived from the derivatives of its volume and enthalpy
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DerivedProperties {
+    /// kJ/(kg K)
+    pub isobaric_heat_capacity: f64,
+    /// kJ/(kg K)
+    pub isochoric_heat_capacity: f64,
+    /// m/s
+    pub speed_of_sound: f64,
+    /// 1/MPa
+    pub isothermal_compressibility: f64,
+    /// 1/K
+    pub volumetric_expansion_coefficient: f64,
+    /// K/MPa
+    pub joule_thomson_coefficient: f64,
+}
+
+impl DerivedProperties {
+    /// Completes the properties from cp, κT and αv at a state
+    ///
+    /// cv = cp - T v αv² / κT, w² = cp / (cv ρ κT) and μJT = v (T αv - 1) / cp
+    pub fn from_heat_capacity_and_coefficients(
+        state: &SinglePhaseState,
+        isobaric_heat_capacity: f64,
+        isothermal_compressibility: f64,
+        volumetric_expansion_coefficient: f64,
+    ) -> DerivedProperties {
+        let temperature = state.temperature + KELVIN_OFFSET;
+        let specific_volume = state.specific_volume;
+
+        // MPa m^3/kg are 1000 kJ/kg
+        let isochoric_heat_capacity = isobaric_heat_capacity
+            - 1000.0 * temperature * specific_volume * volumetric_expansion_coefficient.powi(2)
+                / isothermal_compressibility;
+        let speed_of_sound = (1.0e6 * isobaric_heat_capacity * specific_volume
+            / (isochoric_heat_capacity * isothermal_compressibility))
+            .sqrt();
+        let joule_thomson_coefficient =
+            1000.0 * specific_volume * (temperature * volumetric_expansion_coefficient - 1.0)
+                / isobaric_heat_capacity;
+
+        DerivedProperties {
+            isobaric_heat_capacity,
+            isochoric_heat_capacity,
+            speed_of_sound,
+            isothermal_compressibility,
+            volumetric_expansion_coefficient,
+            joule_thomson_coefficient,
+        }
+    }
+}
+
+/// Derives the properties of `backend` at `pressure` and `temperature` from differences
+/// of its states `pressure_step` and `temperature_step` away
+///
+/// states on the other side of the saturation line or outside the backend are left out,
+/// making the differences one-sided
+pub fn derive_by_differences<B: SinglePhaseProperties + ?Sized>(
+    backend: &B,
+    pressure: f64,
+    temperature: f64,
+    pressure_step: f64,
+    temperature_step: f64,
+) -> Result<DerivedProperties, Error> {
+    let state = backend.single_phase_state(pressure, temperature)?;
+
+    let (density_by_temperature, enthalpy_by_temperature) = slopes(
+        &state,
+        backend.single_phase_state(pressure, temperature - temperature_step),
+        backend.single_phase_state(pressure, temperature + temperature_step),
+        |state| state.temperature,
+    )?;
+    let (density_by_pressure, _) = slopes(
+        &state,
+        backend.single_phase_state(pressure - pressure_step, temperature),
+        backend.single_phase_state(pressure + pressure_step, temperature),
+        |state| state.pressure,
+    )?;
+
+    Ok(DerivedProperties::from_heat_capacity_and_coefficients(
+        &state,
+        enthalpy_by_temperature,
+        density_by_pressure * state.specific_volume,
+        -density_by_temperature * state.specific_volume,
+    ))
+}
+
+/// Slopes of the density and enthalpy against `coordinate` through the states around
+/// `center` as (density, enthalpy), the density of a gas is about linear in pressure
+///
+/// the density is taken as the reciprocal of the specific volume, which every backend holds
+fn slopes(
+    center: &SinglePhaseState,
+    lower: Result<SinglePhaseState, Error>,
+    upper: Result<SinglePhaseState, Error>,
+    coordinate: impl Fn(&SinglePhaseState) -> f64,
+) -> Result<(f64, f64), Error> {
+    let is_liquid = center.phase.is_liquid();
+    let lower = lower
+        .ok()
+        .filter(|state| state.phase.is_liquid() == is_liquid);
+    let upper = upper
+        .ok()
+        .filter(|state| state.phase.is_liquid() == is_liquid);
+
+    let (lower, upper) = match (&lower, &upper) {
+        (Some(lower), Some(upper)) => (lower, upper),
+        (Some(lower), None) => (lower, center),
+        (None, Some(upper)) => (center, upper),
+        // only the state itself lies in the backend on its side of the saturation line
+        (None, None) => {
+            let value = coordinate(center);
+            return Err(Error::ValueOutOfRange(value, value));
+        }
+    };
+
+    let step = coordinate(upper) - coordinate(lower);
+    Ok((
+        (1.0 / upper.specific_volume - 1.0 / lower.specific_volume) / step,
+        (upper.enthalpy - lower.enthalpy) / step,
+    ))
+}
@@ -0,0 +1,234 @@
+use super::phase::Phase;
+use super::state::SinglePhaseState;
+use super::waterpoint::*;
+use crate::error::Error;
+use crate::saturated_steam::MixtureState;
+
+/// State of water found from its pressure and one other property
+#[derive(Debug, Clone, PartialEq)]
+pub enum FlashState {
+    SinglePhase(SinglePhaseState),
+    /// state inside the two-phase region
+    TwoPhase(MixtureState),
+}
+
+impl FlashState {
+    /// quality of the state when it lies inside the two-phase region
+    pub fn quality(&self) -> Option<f64> {
+        match self {
+            FlashState::SinglePhase(_) => None,
+            FlashState::TwoPhase(mixture) => Some(mixture.quality),
+        }
+    }
+
+    pub fn phase(&self) -> Phase {
+        match self {
+            FlashState::SinglePhase(state) => state.phase,
+            FlashState::TwoPhase(_) => Phase::TwoPhase,
+        }
+    }
+
+    pub fn pressure(&self) -> f64 {
+        match self {
+            FlashState::SinglePhase(state) => state.pressure,
+            FlashState::TwoPhase(mixture) => mixture.pressure,
+        }
+    }
+
+    pub fn temperature(&self) -> f64 {
+        match self {
+            FlashState::SinglePhase(state) => state.temperature,
+            FlashState::TwoPhase(mixture) => mixture.temperature,
+        }
+    }
+
+    pub fn specific_volume(&self) -> f64 {
+        match self {
+            FlashState::SinglePhase(state) => state.specific_volume,
+            FlashState::TwoPhase(mixture) => mixture.specific_volume,
+        }
+    }
+
+    pub fn internal_energy(&self) -> f64 {
+        match self {
+            FlashState::SinglePhase(state) => state.internal_energy,
+            FlashState::TwoPhase(mixture) => mixture.internal_energy,
+        }
+    }
+
+    pub fn enthalpy(&self) -> f64 {
+        match self {
+            FlashState::SinglePhase(state) => state.enthalpy,
+            FlashState::TwoPhase(mixture) => mixture.enthalpy,
+        }
+    }
+
+    pub fn entropy(&self) -> f64 {
+        match self {
+            FlashState::SinglePhase(state) => state.entropy,
+            FlashState::TwoPhase(mixture) => mixture.entropy,
+        }
+    }
+}
+
+/// Interpolates the state along an isobar at which the property at `value_index` equals `value`
+///
+/// the isobar has to be sorted by temperature and the property has to increase with temperature
+pub fn interpolate_isobar_at_value(
+    isobar: &[WaterPoint],
+    value_index: usize,
+    value: f64,
+) -> Result<WaterPoint, Error> {
+    let bounding_points = isobar
+        .iter()
+        .zip(isobar.iter().skip(1))
+        .find(|(lower, upper)| {
+            lower.values[value_index] <= value && value <= upper.values[value_index]
+        });
+
+    let (lower, upper) = match bounding_points {
+        Some(points) => points,
+        None => {
+            let smallest_value = isobar[0].values[value_index];
+            let largest_value = isobar[isobar.len() - 1].values[value_index];
+            return Err(Error::ValueOutOfRange(smallest_value, largest_value));
+        }
+    };
+
+    let lower_value = lower.values[value_index];
+    let upper_value = upper.values[value_index];
+
+    if lower_value == upper_value {
+        return Ok(lower.clone());
+    }
+
+    let fraction = (value - lower_value) / (upper_value - lower_value);
+
+    Ok(interpolate_by_fraction(fraction, lower, upper))
+}
+
+/// Interpolates the state on one side of the saturation line of an isobar at which the
+/// property at `value_index` equals `value`
+///
+/// `liquid_side` picks the rows below or above saturation holding `value` so that the search never
+/// bounds the state between the saturated liquid and vapor rows, values past the saturated row are
+/// extrapolated from the two rows next to it since the saturation of the requested pressure
+/// lies beyond the one of the isobar. Isobars without saturation rows, or a `liquid_side` of
+/// `None` above the critical pressure, are searched whole
+pub fn interpolate_isobar_side_at_value(
+    isobar: &[WaterPoint],
+    value_index: usize,
+    value: f64,
+    liquid_side: Option<bool>,
+) -> Result<WaterPoint, Error> {
+    let saturated_vapor_index = isobar
+        .iter()
+        .position(|point| point.phase == Phase::SaturatedVapor);
+
+    let (side, saturated_index, neighbour_index) = match (liquid_side, saturated_vapor_index) {
+        (Some(true), Some(index)) if index >= 2 => (&isobar[..index], index - 1, index - 2),
+        (Some(false), Some(index)) if isobar.len() - index >= 2 => (&isobar[index..], 0, 1),
+        _ => return interpolate_isobar_at_value(isobar, value_index, value),
+    };
+
+    let saturated_value = side[saturated_index].values[value_index];
+    let is_past_saturation = if saturated_index == 0 {
+        value < saturated_value
+    } else {
+        value > saturated_value
+    };
+
+    if !is_past_saturation {
+        return interpolate_isobar_at_value(side, value_index, value);
+    }
+
+    let saturated = &side[saturated_index];
+    let neighbour = &side[neighbour_index];
+    let neighbour_value = neighbour.values[value_index];
+
+    if neighbour_value == saturated_value {
+        return Ok(saturated.clone());
+    }
+
+    let fraction = (value - neighbour_value) / (saturated_value - neighbour_value);
+
+    Ok(WaterPoint {
+        phase: neighbour.phase,
+        ..interpolate_by_fraction(fraction, neighbour, saturated)
+    })
+}
+
+/// Interpolates linearly in pressure between states found on two bounding isobars
+pub fn interpolate_between_isobars(
+    pressure: f64,
+    lower_state: WaterPoint,
+    upper_state: WaterPoint,
+) -> WaterPoint {
+    let lower_pressure = lower_state.point.0;
+    let upper_pressure = upper_state.point.0;
+
+    if lower_pressure == upper_pressure {
+        return lower_state;
+    }
+
+    let fraction = (pressure - lower_pressure) / (upper_pressure - lower_pressure);
+
+    interpolate_by_fraction(fraction, &lower_state, &upper_state)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn water_point(temperature: f64, enthalpy: f64) -> WaterPoint {
+        WaterPoint {
+            point: (1.0, temperature),
+            values: vec![0.0, 0.0, 0.0, enthalpy, 0.0],
+            phase: Phase::SuperheatedVapor,
+        }
+    }
+
+    #[test]
+    fn test_interpolate_isobar_at_value() {
+        let isobar = vec![
+            water_point(100.0, 2000.0),
+            water_point(200.0, 2200.0),
+            water_point(300.0, 2600.0),
+        ];
+
+        let state = interpolate_isobar_at_value(&isobar, 3, 2400.0).unwrap();
+        assert_eq!(state.point.1, 250.0);
+        assert_eq!(state.values[3], 2400.0);
+
+        assert!(interpolate_isobar_at_value(&isobar, 3, 2700.0).is_err());
+    }
+
+    #[test]
+    fn test_interpolate_isobar_side_at_value() {
+        let phase_point = |temperature: f64, enthalpy: f64, phase: Phase| WaterPoint {
+            phase,
+            ..water_point(temperature, enthalpy)
+        };
+        let isobar = vec![
+            phase_point(170.0, 719.2, Phase::CompressedLiquid),
+            phase_point(175.0, 741.08, Phase::CompressedLiquid),
+            phase_point(179.878, 762.52, Phase::SaturatedLiquid),
+            phase_point(179.878, 2777.1, Phase::SaturatedVapor),
+            phase_point(180.0, 2777.4, Phase::SuperheatedVapor),
+            phase_point(185.0, 2790.7, Phase::SuperheatedVapor),
+        ];
+
+        // a value between the saturated rows is not bounded by them
+        let state = interpolate_isobar_side_at_value(&isobar, 3, 771.0, Some(true)).unwrap();
+        assert!((state.point.1 - 181.81).abs() < 0.01);
+        assert_eq!(state.phase, Phase::CompressedLiquid);
+
+        let state = interpolate_isobar_side_at_value(&isobar, 3, 2776.0, Some(false)).unwrap();
+        assert!(state.point.1 < 179.878);
+        assert_eq!(state.phase, Phase::SuperheatedVapor);
+
+        let state = interpolate_isobar_side_at_value(&isobar, 3, 730.14, Some(true)).unwrap();
+        assert!((state.point.1 - 172.5).abs() < 1e-9);
+        assert!(interpolate_isobar_side_at_value(&isobar, 3, 700.0, Some(true)).is_err());
+    }
+}
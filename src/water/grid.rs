@@ -0,0 +1,170 @@
+use std::ops::Range;
+
+use super::waterpoint::WaterPoint;
+use crate::error::Error;
+
+/// Sorted pressure axis of the water table with the range of points on each isobar
+///
+/// built once when the table is loaded, so bounding a query is a binary search,
+/// the isobars do not share their temperatures so each one is searched on its own
+#[derive(Debug, Clone)]
+pub struct WaterGrid {
+    pub pressures: Vec<f64>,
+    /// (minimum, maximum) temperature over all isobars
+    pub temperature_range: (f64, f64),
+    /// range of the table points holding each isobar
+    isobars: Vec<Range<usize>>,
+}
+
+impl WaterGrid {
+    /// Indexes `water_points`, which must be sorted by pressure then temperature
+    pub fn new(water_points: &[WaterPoint]) -> WaterGrid {
+        let mut pressures: Vec<f64> = Vec::new();
+        let mut isobars: Vec<Range<usize>> = Vec::new();
+
+        for (index, water_point) in water_points.iter().enumerate() {
+            match isobars.last_mut() {
+                Some(isobar) if pressures.last() == Some(&water_point.point.0) => {
+                    isobar.end = index + 1;
+                }
+                _ => {
+                    pressures.push(water_point.point.0);
+                    isobars.push(index..index + 1);
+                }
+            }
+        }
+
+        let temperature_range = water_points.iter().fold(
+            (f64::INFINITY, f64::NEG_INFINITY),
+            |(minimum, maximum), water_point| {
+                (
+                    minimum.min(water_point.point.1),
+                    maximum.max(water_point.point.1),
+                )
+            },
+        );
+
+        WaterGrid {
+            pressures,
+            temperature_range,
+            isobars,
+        }
+    }
+
+    /// indices of the isobars bounding `pressure` as (lower, upper)
+    pub fn pressure_cell(&self, pressure: f64) -> (usize, usize) {
+        bounding_indices(&self.pressures, pressure)
+    }
+
+    /// range of the table points on the isobar at `pressure_index`
+    pub fn isobar(&self, pressure_index: usize) -> Range<usize> {
+        self.isobars[pressure_index].clone()
+    }
+}
+
+/// Finds the nodes of a sorted axis on either side of `target` as (lower, upper)
+///
+/// a target on a node is its lower bound, targets outside the axis use the cell at its end
+pub fn bounding_indices(axis: &[f64], target: f64) -> (usize, usize) {
+    if axis.len() < 2 {
+        return (0, 0);
+    }
+
+    let upper = axis
+        .partition_point(|node| *node <= target)
+        .clamp(1, axis.len() - 1);
+
+    (upper - 1, upper)
+}
+
+/// Finds the points of an isobar on either side of `temperature` as (lower, upper)
+///
+/// the saturated liquid and vapor points share the saturation temperature, so the
+/// bounding points always lie on the same side of the saturation line as `temperature`
+pub fn isobar_temperature_bounds(
+    isobar: &[WaterPoint],
+    temperature: f64,
+) -> Result<(usize, usize), Error> {
+    let (minimum, maximum) = match (isobar.first(), isobar.last()) {
+        (Some(first), Some(last)) => (first.point.1, last.point.1),
+        _ => return Err(Error::ValueOutOfRange(f64::NAN, f64::NAN)),
+    };
+
+    if isobar.len() < 2 || !(minimum..=maximum).contains(&temperature) {
+        return Err(Error::ValueOutOfRange(minimum, maximum));
+    }
+
+    let upper = isobar
+        .partition_point(|point| point.point.1 <= temperature)
+        .clamp(1, isobar.len() - 1);
+
+    Ok((upper - 1, upper))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::water::Phase;
+
+    fn water_point(pressure: f64, temperature: f64, phase: Phase) -> WaterPoint {
+        WaterPoint {
+            point: (pressure, temperature),
+            values: vec![],
+            phase,
+        }
+    }
+
+    #[test]
+    fn test_bounding_indices() {
+        let axis = [0.0, 10.0, 20.0, 30.0];
+
+        assert_eq!(bounding_indices(&axis, 15.0), (1, 2));
+        assert_eq!(bounding_indices(&axis, 10.0), (1, 2));
+        assert_eq!(bounding_indices(&axis, 30.0), (2, 3));
+        assert_eq!(bounding_indices(&axis, -5.0), (0, 1));
+        assert_eq!(bounding_indices(&axis, 45.0), (2, 3));
+    }
+
+    #[test]
+    fn test_water_grid() {
+        let water_points = vec![
+            water_point(0.01, 40.0, Phase::CompressedLiquid),
+            water_point(0.01, 45.806, Phase::SaturatedLiquid),
+            water_point(0.01, 45.806, Phase::SaturatedVapor),
+            water_point(0.01, 50.0, Phase::SuperheatedVapor),
+            water_point(0.02, 40.0, Phase::CompressedLiquid),
+            water_point(0.02, 45.0, Phase::CompressedLiquid),
+            water_point(0.02, 50.0, Phase::CompressedLiquid),
+            water_point(0.03, 50.0, Phase::CompressedLiquid),
+        ];
+
+        let grid = WaterGrid::new(&water_points);
+
+        assert_eq!(grid.pressures, vec![0.01, 0.02, 0.03]);
+        assert_eq!(grid.temperature_range, (40.0, 50.0));
+        assert_eq!(grid.isobar(0), 0..4);
+        assert_eq!(grid.isobar(2), 7..8);
+        assert_eq!(grid.pressure_cell(0.025), (1, 2));
+
+        let isobar = &water_points[grid.isobar(0)];
+        assert_eq!(isobar_temperature_bounds(isobar, 42.0).unwrap(), (0, 1));
+        assert_eq!(isobar_temperature_bounds(isobar, 45.806).unwrap(), (2, 3));
+        assert_eq!(isobar_temperature_bounds(isobar, 47.0).unwrap(), (2, 3));
+        assert_eq!(isobar_temperature_bounds(isobar, 50.0).unwrap(), (2, 3));
+
+        let isobar = &water_points[grid.isobar(1)];
+        assert_eq!(isobar_temperature_bounds(isobar, 40.0).unwrap(), (0, 1));
+        assert_eq!(isobar_temperature_bounds(isobar, 45.0).unwrap(), (1, 2));
+        assert!(matches!(
+            isobar_temperature_bounds(isobar, 55.0),
+            Err(Error::ValueOutOfRange(40.0, 50.0))
+        ));
+        assert!(matches!(
+            isobar_temperature_bounds(isobar, f64::NAN),
+            Err(Error::ValueOutOfRange(40.0, 50.0))
+        ));
+
+        let isobar = &water_points[grid.isobar(2)];
+        assert!(isobar_temperature_bounds(isobar, 50.0).is_err());
+    }
+}
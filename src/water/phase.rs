@@ -0,0 +1,94 @@
+use std::{fmt::Display, str::FromStr};
+
+use crate::error::Error;
+
+/// Phase of water at a point of the water table
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Phase {
+    CompressedLiquid,
+    SaturatedLiquid,
+    SaturatedVapor,
+    /// mixture of saturated liquid and saturated vapor
+    TwoPhase,
+    SuperheatedVapor,
+    Supercritical,
+    /// ice Ih
+    Solid,
+}
+
+impl Phase {
+    /// compressed or saturated liquid, the side of the saturation line below the saturation temperature
+    pub fn is_liquid(self) -> bool {
+        matches!(self, Phase::CompressedLiquid | Phase::SaturatedLiquid)
+    }
+
+    pub fn is_saturated(self) -> bool {
+        matches!(self, Phase::SaturatedLiquid | Phase::SaturatedVapor)
+    }
+}
+
+impl FromStr for Phase {
+    type Err = Error;
+
+    /// Parses the phase tokens of the water table, surrounding quotes are ignored
+    fn from_str(token: &str) -> Result<Self, Self::Err> {
+        let phase = token.trim().trim_matches('"').trim().to_lowercase();
+
+        match phase.as_str() {
+            "liquid" | "compressed liquid" => Ok(Phase::CompressedLiquid),
+            "saturated liquid" => Ok(Phase::SaturatedLiquid),
+            "saturated vapor" => Ok(Phase::SaturatedVapor),
+            "saturated mixture" | "two-phase" => Ok(Phase::TwoPhase),
+            "vapor" | "superheated vapor" => Ok(Phase::SuperheatedVapor),
+            "supercritical fluid" | "supercritical" => Ok(Phase::Supercritical),
+            "solid" | "ice" => Ok(Phase::Solid),
+            _ => Err(Error::table_parsing(None, None, token, "unknown phase")),
+        }
+    }
+}
+
+impl Display for Phase {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let name = match self {
+            Phase::CompressedLiquid => "compressed liquid",
+            Phase::SaturatedLiquid => "saturated liquid",
+            Phase::SaturatedVapor => "saturated vapor",
+            Phase::TwoPhase => "saturated mixture",
+            Phase::SuperheatedVapor => "superheated vapor",
+            Phase::Supercritical => "supercritical fluid",
+            Phase::Solid => "solid",
+        };
+
+        write!(f, "{}", name)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_phase() {
+        assert_eq!(
+            " \"liquid\"".parse::<Phase>().unwrap(),
+            Phase::CompressedLiquid
+        );
+        assert_eq!(
+            "\"vapor\"".parse::<Phase>().unwrap(),
+            Phase::SuperheatedVapor
+        );
+        assert_eq!(
+            "\"supercritical fluid\"".parse::<Phase>().unwrap(),
+            Phase::Supercritical
+        );
+        assert_eq!(
+            Phase::SaturatedVapor.to_string().parse::<Phase>().unwrap(),
+            Phase::SaturatedVapor
+        );
+
+        assert!(matches!(
+            "\"plasma\"".parse::<Phase>(),
+            Err(Error::TableParsingError(_))
+        ));
+    }
+}
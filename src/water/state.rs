@@ -0,0 +1,116 @@
+use super::derived::DerivedProperties;
+use super::phase::Phase;
+use crate::error::Error;
+use crate::math::{ColumnTransform, Transform};
+use crate::saturated_steam::MixtureProperty;
+
+/// Properties of water held by the columns of the water table
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WaterProperty {
+    SpecificVolume,
+    Density,
+    InternalEnergy,
+    Enthalpy,
+    Entropy,
+}
+
+impl WaterProperty {
+    pub const ALL: [WaterProperty; 5] = [
+        WaterProperty::SpecificVolume,
+        WaterProperty::Density,
+        WaterProperty::InternalEnergy,
+        WaterProperty::Enthalpy,
+        WaterProperty::Entropy,
+    ];
+
+    /// start of the header of the column holding the property
+    pub fn header_label(self) -> &'static str {
+        match self {
+            WaterProperty::SpecificVolume => "Specific Volume",
+            WaterProperty::Density => "Density",
+            WaterProperty::InternalEnergy => "Specific Internal Energy",
+            WaterProperty::Enthalpy => "Specific Enthalpy",
+            WaterProperty::Entropy => "Specific Entropy",
+        }
+    }
+
+    /// Transform of the column values when interpolating
+    ///
+    /// vapor specific volume varies about inversely with pressure, so it is interpolated as density
+    pub fn column_transform(self) -> ColumnTransform {
+        match self {
+            WaterProperty::SpecificVolume => {
+                ColumnTransform::new(Transform::Identity, Transform::Reciprocal)
+            }
+            _ => ColumnTransform::IDENTITY,
+        }
+    }
+}
+
+impl From<MixtureProperty> for WaterProperty {
+    fn from(property: MixtureProperty) -> Self {
+        match property {
+            MixtureProperty::SpecificVolume => WaterProperty::SpecificVolume,
+            MixtureProperty::InternalEnergy => WaterProperty::InternalEnergy,
+            MixtureProperty::Enthalpy => WaterProperty::Enthalpy,
+            MixtureProperty::Entropy => WaterProperty::Entropy,
+        }
+    }
+}
+
+/// Finds the index in `WaterPoint::values` of every water property from the table headers
+///
+/// the first two columns hold pressure and temperature, the returned vector
+/// is indexed by `WaterProperty as usize`
+pub fn find_value_columns(headers: &[String], header_line: usize) -> Result<Vec<usize>, Error> {
+    WaterProperty::ALL
+        .iter()
+        .map(|property| {
+            headers
+                .iter()
+                .skip(2)
+                .position(|header| header.starts_with(property.header_label()))
+                .ok_or_else(|| {
+                    Error::table_parsing(
+                        Some(header_line),
+                        None,
+                        property.header_label(),
+                        "missing column",
+                    )
+                })
+        })
+        .collect()
+}
+
+/// Finds the transform of every value of the table from the columns of its properties
+///
+/// the returned vector is indexed like `WaterPoint::values`
+pub fn find_value_transforms(columns: &[usize], number_of_values: usize) -> Vec<ColumnTransform> {
+    let mut transforms = vec![ColumnTransform::IDENTITY; number_of_values];
+
+    for property in WaterProperty::ALL {
+        transforms[columns[property as usize]] = property.column_transform();
+    }
+
+    transforms
+}
+
+/// Properties of compressed liquid water, superheated steam or supercritical fluid
+#[derive(Debug, Clone, PartialEq)]
+pub struct SinglePhaseState {
+    pub pressure: f64,
+    pub temperature: f64,
+    pub specific_volume: f64,
+    pub density: f64,
+    pub internal_energy: f64,
+    pub enthalpy: f64,
+    pub entropy: f64,
+    /// h - T s
+    pub gibbs_energy: f64,
+    /// u - T s
+    pub helmholtz_energy: f64,
+    pub phase: Phase,
+    /// heat capacities, speed of sound and coefficients of the state where the backend
+    /// computes them with the state, the water table only derives them when asked for
+    pub derived_properties: Option<DerivedProperties>,
+}
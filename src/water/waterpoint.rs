@@ -1,139 +1,399 @@
+use super::phase::Phase;
 use crate::error::Error;
 use crate::math::*;
+use crate::InterpolationMode;
 
 #[derive(Debug, Clone)]
 pub struct WaterPoint {
     /// point (Pressure, Temperature)
-    pub point: (f32, f32),
-    pub values: Vec<f32>,
-    pub phase: String,
+    pub point: (f64, f64),
+    pub values: Vec<f64>,
+    pub phase: Phase,
 }
 
-pub fn parse_to_water_point_struct(lines: Vec<String>) -> Result<Vec<WaterPoint>, Error> {
-    let water_points: Vec<WaterPoint> = lines
-        .iter()
-        .map(|line| {
-            let mut values = line.split(',');
-            let point0 = values.next().unwrap().trim().parse::<f32>();
-            let point1 = values.next().unwrap().trim().parse::<f32>();
+/// How the values of a query of the water table were found
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InterpolationMethod {
+    /// interpolated between the points of the cell around the query
+    Cell,
+    /// interpolated between an isobar and the saturation line crossing the cell
+    AcrossSaturation,
+    /// the nearest point of the cell, the saturation line is not known at the temperature
+    NearestPoint,
+}
 
-            let mut point_values: Vec<f32> = Vec::new();
-            let mut phase = String::new();
+/// Interpolated point with the corners of its cell and an estimate of the interpolation error
+#[derive(Debug, Clone)]
+pub struct EstimatedWaterPoint {
+    pub water_point: WaterPoint,
+    /// (pressure, temperature) of the corners of the cell, ordered as in `interpolate_water_points`
+    pub corners: [(f64, f64); 4],
+    /// (pressure, temperature) size of the cell, the temperature is the wider of its two isobars
+    pub cell_size: (f64, f64),
+    /// estimated error of every value, the difference between linear and quadratic
+    /// interpolation, `None` when there are too few points or the cell was not interpolated
+    pub errors: Option<Vec<f64>>,
+    pub method: InterpolationMethod,
+}
 
-            for point_value in values {
-                let float_value = point_value.trim().parse::<f32>();
+impl EstimatedWaterPoint {
+    /// whether the point is a table point returned by the nearest point fallback
+    pub fn is_nearest_point(&self) -> bool {
+        self.method == InterpolationMethod::NearestPoint
+    }
+}
 
-                if let Ok(value) = float_value {
-                    point_values.push(value);
-                } else {
-                    phase = point_value.to_owned().to_string();
-                }
+/// Parses the data lines of the water table, paired with their line numbers,
+/// every line must hold `number_of_columns` fields ending with the phase
+pub fn parse_to_water_point_struct(
+    lines: Vec<crate::NumberedLine<'_>>,
+    number_of_columns: usize,
+) -> Result<Vec<WaterPoint>, Error> {
+    lines
+        .iter()
+        .map(|(line_number, line)| {
+            let mut fields: Vec<&str> = line.split(',').collect();
+            if fields.len() != number_of_columns || number_of_columns < 3 {
+                let message = format!(
+                    "expected {} columns, found {}",
+                    number_of_columns,
+                    fields.len()
+                );
+                return Err(Error::table_parsing(
+                    Some(*line_number),
+                    None,
+                    line,
+                    &message,
+                ));
             }
 
-            WaterPoint {
-                point: (point0.unwrap(), point1.unwrap()),
-                values: point_values,
+            // the phase is held by the last column of the table
+            let phase_field = fields.pop().unwrap();
+            let phase = phase_field.parse::<Phase>().map_err(|_| {
+                Error::table_parsing(
+                    Some(*line_number),
+                    Some(number_of_columns),
+                    phase_field,
+                    "unknown phase",
+                )
+            })?;
+
+            let mut values = fields
+                .iter()
+                .enumerate()
+                .map(|(index, field)| crate::parse_table_float(field, *line_number, index + 1));
+
+            let pressure = values.next().unwrap()?;
+            let temperature = values.next().unwrap()?;
+            let values = values.collect::<Result<Vec<f64>, Error>>()?;
+
+            Ok(WaterPoint {
+                point: (pressure, temperature),
+                values,
                 phase,
-            }
+            })
         })
+        .collect()
+}
+
+/// Interpolates linearly between two points, `fraction` is 0 at `lower` and 1 at `upper`
+pub fn interpolate_by_fraction(
+    fraction: f64,
+    lower: &WaterPoint,
+    upper: &WaterPoint,
+) -> WaterPoint {
+    let interpolate = |lower_value: f64, upper_value: f64| {
+        linear_interpolate(fraction, Point2(0.0, lower_value), Point2(1.0, upper_value))
+    };
+
+    let values = lower
+        .values
+        .iter()
+        .zip(&upper.values)
+        .map(|(lower_value, upper_value)| interpolate(*lower_value, *upper_value))
         .collect();
 
-    Ok(water_points)
+    let phase = interpolated_phase(fraction, lower.phase, upper.phase);
+
+    WaterPoint {
+        point: (
+            interpolate(lower.point.0, upper.point.0),
+            interpolate(lower.point.1, upper.point.1),
+        ),
+        values,
+        phase,
+    }
+}
+
+/// Phase of a point interpolated between points of phases `lower` and `upper`
+///
+/// a saturated node only bounds the single phase region next to it, so points
+/// off the node take the phase of the other end
+fn interpolated_phase(fraction: f64, lower: Phase, upper: Phase) -> Phase {
+    let lower_bounds_upper = lower.is_saturated() && !upper.is_saturated();
+    let upper_bounds_lower = upper.is_saturated() && !lower.is_saturated();
+
+    if fraction <= 0.0 {
+        lower
+    } else if fraction >= 1.0 || lower_bounds_upper {
+        upper
+    } else if upper_bounds_lower || fraction < 0.5 {
+        lower
+    } else {
+        upper
+    }
+}
+
+/// Interpolates linearly in temperature between two points of the same isobar
+pub fn interpolate_along_isobar(
+    temperature: f64,
+    lower: &WaterPoint,
+    upper: &WaterPoint,
+) -> WaterPoint {
+    if lower.point.1 == upper.point.1 {
+        return lower.clone();
+    }
+
+    let fraction = (temperature - lower.point.1) / (upper.point.1 - lower.point.1);
+    interpolate_by_fraction(fraction, lower, upper)
 }
 
+/// Interpolates every value at `x` through `water_points` with `mode` and the transform
+/// of its column, `coordinate` gives the position of each point which has to increase along the slice
+pub fn interpolate_values_with_mode(
+    mode: InterpolationMode,
+    x: f64,
+    water_points: &[&WaterPoint],
+    coordinate: impl Fn(&WaterPoint) -> f64,
+    transforms: &[ColumnTransform],
+) -> Vec<f64> {
+    let positions: Vec<f64> = water_points
+        .iter()
+        .map(|water_point| coordinate(water_point))
+        .collect();
+
+    (0..water_points[0].values.len())
+        .map(|index| {
+            let values: Vec<f64> = water_points
+                .iter()
+                .map(|water_point| water_point.values[index])
+                .collect();
+
+            transforms
+                .get(index)
+                .copied()
+                .unwrap_or_default()
+                .interpolate(mode, x, &positions, &values)
+        })
+        .collect()
+}
+
+/// Interpolates double linearly between the corners of a cell, the values at `index`
+/// are interpolated with the value transform of `transforms[index]` when it is given
 pub fn interpolate_water_points(
-    pressure: f32,
-    temperature: f32,
+    pressure: f64,
+    temperature: f64,
     water_point_0_0: WaterPoint,
     water_point_0_1: WaterPoint,
     water_point_1_0: WaterPoint,
     water_point_1_1: WaterPoint,
+    transforms: &[ColumnTransform],
 ) -> WaterPoint {
     let number_of_values = water_point_0_0.values.len();
+    let corners = [
+        &water_point_0_0,
+        &water_point_0_1,
+        &water_point_1_0,
+        &water_point_1_1,
+    ];
 
-    let values: Vec<f32> = (0..number_of_values)
+    let values: Vec<f64> = (0..number_of_values)
         .map(|index| {
-            let point_0_0 = Point3(
-                water_point_0_0.point.0,
-                water_point_0_0.point.1,
-                water_point_0_0.values[index],
-            );
-
-            let point_0_1 = Point3(
-                water_point_0_1.point.0,
-                water_point_0_1.point.1,
-                water_point_0_1.values[index],
-            );
-
-            let point_1_0 = Point3(
-                water_point_1_0.point.0,
-                water_point_1_0.point.1,
-                water_point_1_0.values[index],
-            );
-
-            let point_1_1 = Point3(
-                water_point_1_1.point.0,
-                water_point_1_1.point.1,
-                water_point_1_1.values[index],
-            );
-
-            double_linear_interpolate(
-                pressure,
-                temperature,
-                point_0_0,
-                point_0_1,
-                point_1_0,
-                point_1_1,
-            )
+            let transform = transforms
+                .get(index)
+                .map_or(Transform::Identity, |t| t.value);
+            let corner_values = corners.map(|water_point| water_point.values[index]);
+
+            // corners are returned exactly rather than through the round trip of the transform
+            if let Some(corner) = corners
+                .iter()
+                .position(|water_point| water_point.point == (pressure, temperature))
+            {
+                return corner_values[corner];
+            }
+
+            let transformed_values = corner_values.map(|value| transform.apply(value));
+            if transformed_values.iter().all(|value| value.is_finite()) {
+                let value = transform.invert(interpolate_corners(
+                    pressure,
+                    temperature,
+                    &corners,
+                    transformed_values,
+                ));
+                if value.is_finite() {
+                    return value;
+                }
+            }
+
+            interpolate_corners(pressure, temperature, &corners, corner_values)
         })
         .collect();
 
+    // saturated corners only bound the cell, which lies on one side of the saturation line
+    let phase = [
+        &water_point_0_0,
+        &water_point_0_1,
+        &water_point_1_0,
+        &water_point_1_1,
+    ]
+    .iter()
+    .map(|water_point| water_point.phase)
+    .find(|phase| !phase.is_saturated())
+    .unwrap_or(water_point_0_0.phase);
+
     WaterPoint {
         point: (pressure, temperature),
         values,
-        phase: water_point_0_0.phase,
+        phase,
     }
 }
 
+/// Interpolates `values` held at `corners` double linearly
+fn interpolate_corners(
+    pressure: f64,
+    temperature: f64,
+    corners: &[&WaterPoint; 4],
+    values: [f64; 4],
+) -> f64 {
+    let [point_0_0, point_0_1, point_1_0, point_1_1] = [0, 1, 2, 3].map(|corner| {
+        Point3(
+            corners[corner].point.0,
+            corners[corner].point.1,
+            values[corner],
+        )
+    });
+
+    double_linear_interpolate(
+        pressure,
+        temperature,
+        point_0_0,
+        point_0_1,
+        point_1_0,
+        point_1_1,
+    )
+}
+
+/// Whether the saturation line crosses the cell of the points, saturated points lie on
+/// the side of the phase they bound
 pub fn phase_change_occurs(water_points: (WaterPoint, WaterPoint, WaterPoint, WaterPoint)) -> bool {
-    if water_points.0.phase != water_points.1.phase
-        || water_points.0.phase != water_points.2.phase
-        || water_points.0.phase != water_points.3.phase
-    {
-        return true;
-    }
+    let is_liquid = water_points.0.phase.is_liquid();
 
-    false
+    water_points.1.phase.is_liquid() != is_liquid
+        || water_points.2.phase.is_liquid() != is_liquid
+        || water_points.3.phase.is_liquid() != is_liquid
 }
 
+/// Corner of the cell nearest to `pressure` and `temperature`
+///
+/// the distance along each axis is measured in spans of the cell, since megapascals
+/// and degrees cannot be added to one another
 pub fn get_nearest_water_point(
-    pressure: f32,
-    temperature: f32,
+    pressure: f64,
+    temperature: f64,
     water_points: (WaterPoint, WaterPoint, WaterPoint, WaterPoint),
 ) -> WaterPoint {
-    let mut nearest = water_points.0.clone();
-    let mut cur_score =
-        (nearest.point.0 - pressure).powi(2) + (nearest.point.1 - temperature).powi(2);
-
-    let new_score = (water_points.1.point.0 - pressure).powi(2)
-        + (water_points.1.point.1 - temperature).powi(2);
-    if new_score < cur_score {
-        nearest = water_points.1.clone();
-        cur_score = new_score;
-    }
+    let corners = [
+        water_points.0,
+        water_points.1,
+        water_points.2,
+        water_points.3,
+    ];
 
-    let new_score = (water_points.2.point.0 - pressure).powi(2)
-        + (water_points.2.point.1 - temperature).powi(2);
-    if new_score < cur_score {
-        nearest = water_points.2.clone();
-        cur_score = new_score;
-    }
+    let span = |coordinate: fn(&WaterPoint) -> f64| {
+        let (smallest, largest) = corners.iter().map(coordinate).fold(
+            (f64::INFINITY, f64::NEG_INFINITY),
+            |(smallest, largest), value| (smallest.min(value), largest.max(value)),
+        );
+        if largest > smallest {
+            largest - smallest
+        } else {
+            1.0
+        }
+    };
+    let pressure_span = span(|water_point| water_point.point.0);
+    let temperature_span = span(|water_point| water_point.point.1);
+
+    let distance = |water_point: &WaterPoint| {
+        ((water_point.point.0 - pressure) / pressure_span).powi(2)
+            + ((water_point.point.1 - temperature) / temperature_span).powi(2)
+    };
+
+    let [first, others @ ..] = corners;
+    others.into_iter().fold(first, |nearest, corner| {
+        if distance(&corner) < distance(&nearest) {
+            corner
+        } else {
+            nearest
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_to_water_point_struct_errors() {
+        let lines = vec![(8, "700, 10, 0.00083548, 1196.9, \"liquid\"")];
+        assert_eq!(
+            parse_to_water_point_struct(lines, 5).unwrap()[0]
+                .values
+                .len(),
+            2
+        );
 
-    let new_score = (water_points.3.point.0 - pressure).powi(2)
-        + (water_points.3.point.1 - temperature).powi(2);
-    if new_score < cur_score {
-        nearest = water_points.3;
+        let blank_field = vec![(9, "700, 0, 0, , \"liquid\"")];
+        match parse_to_water_point_struct(blank_field, 5).unwrap_err() {
+            Error::TableParsingError(error) => {
+                assert_eq!(error.line, Some(9));
+                assert_eq!(error.column, Some(4));
+                assert_eq!(error.text, "");
+            }
+            error => panic!("unexpected error {:?}", error),
+        }
+
+        let unknown_phase = vec![(10, "700, 10, 0.00083548, 1196.9, \"plasma\"")];
+        match parse_to_water_point_struct(unknown_phase, 5).unwrap_err() {
+            Error::TableParsingError(error) => {
+                assert_eq!(error.column, Some(5));
+                assert_eq!(error.text, "\"plasma\"");
+            }
+            error => panic!("unexpected error {:?}", error),
+        }
+
+        let missing_column = vec![(11, "700, 10, 0.00083548, \"liquid\"")];
+        assert!(parse_to_water_point_struct(missing_column, 5).is_err());
     }
 
-    nearest
+    #[test]
+    fn test_get_nearest_water_point() {
+        let water_point = |pressure: f64, temperature: f64, phase: Phase| WaterPoint {
+            point: (pressure, temperature),
+            values: vec![],
+            phase,
+        };
+        let water_points = (
+            water_point(1.0, 170.0, Phase::CompressedLiquid),
+            water_point(1.0, 179.878, Phase::SaturatedLiquid),
+            water_point(1.1, 180.0, Phase::CompressedLiquid),
+            water_point(1.1, 184.062, Phase::SaturatedLiquid),
+        );
+
+        // a tenth of a degree away from the 1.1 MPa corner, but most of the cell away in pressure
+        let nearest = get_nearest_water_point(1.02, 180.0, water_points.clone());
+        assert_eq!(nearest.point, (1.0, 179.878));
+
+        let nearest = get_nearest_water_point(1.08, 183.0, water_points);
+        assert_eq!(nearest.point, (1.1, 184.062));
+    }
 }